@@ -0,0 +1,71 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Benchmarks parsing large functions with syn-mid and with syn "full".
+//
+// Run with:
+//
+//     cargo bench --manifest-path bench/Cargo.toml
+
+use std::fmt::Write as _;
+
+use criterion::{BatchSize, Criterion, Throughput, criterion_group, criterion_main};
+use proc_macro2::TokenStream;
+
+/// Returns a function with `n` statements of various kinds.
+fn item_fn(n: usize) -> TokenStream {
+    let mut src = String::from("fn f<T: Clone>(&self, x: Option<T>, (a, b): (u8, u8)) -> u32 {\n");
+    for i in 0..n {
+        let _ = match i % 8 {
+            0 => writeln!(src, "let v{i}: u32 = {i};"),
+            1 => writeln!(src, "let (c{i}, mut d{i}) = (a + {i}, b);"),
+            2 => writeln!(src, "if a > b {{ g(a, {i}); }} else {{ g(b, {i}); }}"),
+            3 => {
+                writeln!(src, "match x {{ Some(ref v) => h(v), None if a == 0 => {{}} _ => {{}} }}")
+            }
+            4 => writeln!(src, "for j in 0..{i} {{ self.k(j).await?; }}"),
+            5 => writeln!(src, "let S {{ s, t: (_, u), .. }} = S::new({i}) else {{ return 0; }};"),
+            6 => writeln!(src, "println!(\"{{}} {{}}\", a, {i});"),
+            _ => writeln!(src, "fn inner{i}(y: u8) -> u8 {{ y * 2 }}"),
+        };
+    }
+    src.push_str("0\n}\n");
+    src.parse().unwrap()
+}
+
+fn parse_item_fn(c: &mut Criterion) {
+    for n in [100, 1_000, 10_000] {
+        let tokens = item_fn(n);
+        let mut group = c.benchmark_group(format!("item_fn/{n}"));
+        group.throughput(Throughput::Elements(n as u64));
+        group.bench_function("syn", |b| {
+            b.iter_batched(
+                || tokens.clone(),
+                |tokens| syn::parse2::<syn::ItemFn>(tokens).unwrap(),
+                BatchSize::LargeInput,
+            );
+        });
+        group.bench_function("syn-mid", |b| {
+            b.iter_batched(
+                || tokens.clone(),
+                |tokens| syn::parse2::<syn_mid::ItemFn>(tokens).unwrap(),
+                BatchSize::LargeInput,
+            );
+        });
+        // Statements are kept as tokens by syn-mid, so also measure splitting
+        // them, which is what a macro that looks at them pays.
+        group.bench_function("syn-mid+split_stmts", |b| {
+            b.iter_batched(
+                || tokens.clone(),
+                |tokens| {
+                    let item = syn::parse2::<syn_mid::ItemFn>(tokens).unwrap();
+                    item.block.split_stmts().unwrap()
+                },
+                BatchSize::LargeInput,
+            );
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(benches, parse_item_fn);
+criterion_main!(benches);
@@ -0,0 +1,79 @@
+#!/usr/bin/env bash
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+set -CeEuo pipefail
+IFS=$'\n\t'
+trap -- 'printf >&2 "%s\n" "${0##*/}: trapped SIGINT"; exit 1' SIGINT
+cd -- "$(dirname -- "$0")"/..
+
+# USAGE:
+#    ./bench/build-time.sh [RUNS] [CARGO_BUILD_OPTIONS]...
+#
+# Measures the clean build time of a minimal proc-macro crate that parses and
+# prints an `ItemFn`, once with syn "full" and once with syn-mid. Each crate is
+# built RUNS times (default: 3) after `cargo clean`.
+#
+# Examples:
+#    ./bench/build-time.sh
+#    ./bench/build-time.sh 5 --release
+
+runs="${1:-3}"
+shift || true
+if [[ ! "${runs}" =~ ^[0-9]+$ ]]; then
+  printf >&2 "%s\n" "error: RUNS must be a number, but got '${runs}'"
+  exit 1
+fi
+
+syn_mid=$(pwd)
+tmp=$(mktemp -d)
+trap -- 'rm -rf -- "${tmp:?}"' EXIT
+
+# create_crate <name> <ItemFn path> <dependencies>
+create_crate() {
+  mkdir -p -- "${tmp}/$1/src"
+  cat >"${tmp}/$1/Cargo.toml" <<EOF2
+[package]
+name = "$1"
+edition = "2021"
+
+[lib]
+proc-macro = true
+
+[dependencies]
+quote = "1"
+$3
+
+[workspace]
+EOF2
+  cat >"${tmp}/$1/src/lib.rs" <<EOF2
+use proc_macro::TokenStream;
+use quote::ToTokens as _;
+
+#[proc_macro_attribute]
+pub fn attr(_args: TokenStream, input: TokenStream) -> TokenStream {
+    let item = syn::parse_macro_input!(input as $2);
+    item.into_token_stream().into()
+}
+EOF2
+  cargo generate-lockfile -q --manifest-path "${tmp}/$1/Cargo.toml"
+}
+
+create_crate with-syn 'syn::ItemFn' 'syn = { version = "2", features = ["full"] }'
+create_crate with-syn-mid 'syn_mid::ItemFn' "syn = \"2\"
+syn-mid = { path = \"${syn_mid}\" }"
+
+TIMEFORMAT='%R'
+for name in with-syn with-syn-mid; do
+  manifest="${tmp}/${name}/Cargo.toml"
+  times=()
+  for ((i = 0; i < runs; i++)); do
+    cargo clean -q --manifest-path "${manifest}"
+    if ! t=$({ time cargo build -q --manifest-path "${manifest}" "$@" >|"${tmp}/log" 2>&1; } 2>&1); then
+      cat >&2 -- "${tmp}/log"
+      exit 1
+    fi
+    times+=("${t}s")
+  done
+  IFS=' '
+  printf "%-13s %s\n" "${name}:" "${times[*]}"
+  IFS=$'\n\t'
+done
@@ -0,0 +1,9 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use syn::parse::Parse as _;
+use syn_mid::FnArg;
+
+fuzz_target!(|data: &[u8]| syn_mid_fuzz::roundtrip(data, FnArg::parse));
@@ -0,0 +1,8 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use syn_mid::Pat;
+
+fuzz_target!(|data: &[u8]| syn_mid_fuzz::roundtrip(data, Pat::parse_single));
@@ -0,0 +1,36 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Harness shared by the fuzz targets.
+//
+// Run a target with cargo-fuzz, e.g.:
+//
+//     cargo +nightly fuzz run pat
+//
+// fuzz/corpus/<target> is seeded with the patterns, arguments, signatures, and
+// functions in the source code of syn and in tests/corpus.
+
+use std::str;
+
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::parse::{ParseStream, Parser as _, Result};
+
+/// Parses `data` with `parser`, and checks that the node it accepts prints
+/// tokens that are parsed back into a node that prints the same tokens.
+///
+/// # Panics
+///
+/// Panics if the round-trip fails, or if `parser` panics. The fuzzer reports
+/// both as crashes.
+pub fn roundtrip<T: ToTokens>(data: &[u8], parser: fn(ParseStream<'_>) -> Result<T>) {
+    let Ok(src) = str::from_utf8(data) else { return };
+    let Ok(tokens) = src.parse::<TokenStream>() else { return };
+    let Ok(node) = parser.parse2(tokens) else { return };
+
+    let printed = node.to_token_stream();
+    let reparsed = match parser.parse2(printed.clone()) {
+        Ok(node) => node.to_token_stream(),
+        Err(e) => panic!("failed to parse printed tokens `{printed}` of `{src}`: {e}"),
+    };
+    assert_eq!(printed.to_string(), reparsed.to_string(), "input: `{src}`");
+}
@@ -0,0 +1,596 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Arbitrary impls for generating random syntax trees.
+//
+// The generated trees print to tokens that the parsers of this crate accept,
+// and parsing these tokens gives the same tree, except for spans. So the
+// generators only build the shapes that the parsers produce: the receiver is
+// always the first argument, one-element tuples always have a trailing comma,
+// `|` and `..` patterns only appear where `Pat::parse_multi_with_leading_vert`
+// is used, and attributes only appear where they are parsed.
+//
+// The data structures of syn, such as types and paths, are built from a small
+// subset of their syntax, and the tokens that are not parsed, such as
+// expressions, are identifiers, literals, `match` expressions, and nested
+// functions.
+
+use alloc::{
+    boxed::Box,
+    string::{String, ToString as _},
+    vec,
+    vec::Vec,
+};
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+use proc_macro2::{Span, TokenStream};
+use quote::ToTokens as _;
+use syn::{
+    Abi, AttrStyle, Attribute, ExprLit, ExprPath, GenericParam, Generics, Ident, Index, Lifetime,
+    LifetimeParam, Lit, LitInt, LitStr, Macro, MacroDelimiter, Member, Meta, Path, ReturnType,
+    Token, Type, TypeParam, TypePath, TypeReference, TypeSlice, TypeTuple, VisRestricted,
+    Visibility, punctuated::Punctuated, token,
+};
+
+use crate::{
+    Arm, Block, ExprMatch, FieldPat, FnArg, ItemFn, Local, LocalInit, Pat, PatIdent, PatOr,
+    PatReference, PatRest, PatStruct, PatTuple, PatTupleStruct, PatType, PatWild, Receiver,
+    Signature, Stmt, StmtMacro, Variadic,
+};
+
+/// The maximum nesting depth of generated patterns, types, and blocks.
+const MAX_DEPTH: u32 = 3;
+
+macro_rules! impl_arbitrary {
+    ($($ty:ident => $f:expr,)*) => {$(
+        impl<'a> Arbitrary<'a> for $ty {
+            fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+                $f(u, MAX_DEPTH)
+            }
+        }
+    )*};
+}
+
+impl_arbitrary! {
+    ItemFn => item_fn,
+    Block => block,
+    Signature => signature,
+    FnArg => fn_arg,
+    Receiver => receiver,
+    Variadic => variadic,
+    // Accepted by `Pat::parse_multi_with_leading_vert`.
+    Pat => |u, depth| pat(u, depth, true, false),
+    PatIdent => |u, _| pat_ident(u),
+    PatOr => pat_or,
+    PatReference => pat_reference,
+    PatRest => |_, _| Ok(pat_rest()),
+    PatStruct => pat_struct,
+    PatTuple => pat_tuple,
+    PatTupleStruct => pat_tuple_struct,
+    PatType => pat_type,
+    PatWild => |_, _| Ok(pat_wild()),
+    FieldPat => field_pat,
+    ExprMatch => expr_match,
+    // Accepted by `Arm::parse` at the end of the input.
+    Arm => |u, depth| arm(u, depth, true),
+    // Accepted by `Stmt::parse`, which requires a trailing semicolon.
+    Stmt => |u, depth| stmt(u, depth, false),
+    Local => local,
+    LocalInit => local_init,
+    StmtMacro => |u, _| stmt_macro(u),
+}
+
+// -----------------------------------------------------------------------------
+// Tokens and the data structures of syn
+
+// https://doc.rust-lang.org/reference/keywords.html
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+fn ident(u: &mut Unstructured<'_>) -> Result<Ident> {
+    let len = u.int_in_range(1..=6)?;
+    let mut s = String::with_capacity(len + 1);
+    for _ in 0..len {
+        s.push(char::from(u.int_in_range(b'a'..=b'z')?));
+    }
+    if KEYWORDS.contains(&&*s) {
+        s.push('_');
+    }
+    Ok(Ident::new(&s, Span::call_site()))
+}
+
+fn lifetime(u: &mut Unstructured<'_>) -> Result<Lifetime> {
+    Ok(Lifetime::new(&["'", &ident(u)?.to_string()].concat(), Span::call_site()))
+}
+
+fn lit(u: &mut Unstructured<'_>) -> Result<ExprLit> {
+    let span = Span::call_site();
+    let lit = if u.arbitrary()? {
+        Lit::Int(LitInt::new(&u.arbitrary::<u32>()?.to_string(), span))
+    } else {
+        Lit::Str(LitStr::new(u.arbitrary()?, span))
+    };
+    Ok(ExprLit { attrs: vec![], lit })
+}
+
+fn path(u: &mut Unstructured<'_>, min_segments: usize) -> Result<Path> {
+    let mut segments = Punctuated::new();
+    for _ in 0..u.int_in_range(min_segments..=min_segments + 1)? {
+        segments.push(ident(u)?.into());
+    }
+    Ok(Path { leading_colon: None, segments })
+}
+
+fn attrs(u: &mut Unstructured<'_>, inner: bool) -> Result<Vec<Attribute>> {
+    let span = Span::call_site();
+    let mut attrs = vec![];
+    for _ in 0..u.int_in_range(0..=2)? {
+        attrs.push(Attribute {
+            pound_token: Token![#](span),
+            style: if inner { AttrStyle::Inner(Token![!](span)) } else { AttrStyle::Outer },
+            bracket_token: token::Bracket(span),
+            meta: Meta::Path(ident(u)?.into()),
+        });
+    }
+    Ok(attrs)
+}
+
+fn visibility(u: &mut Unstructured<'_>) -> Result<Visibility> {
+    let span = Span::call_site();
+    Ok(match u.int_in_range(0..=2)? {
+        0 => Visibility::Inherited,
+        1 => Visibility::Public(Token![pub](span)),
+        _ => Visibility::Restricted(VisRestricted {
+            pub_token: Token![pub](span),
+            paren_token: token::Paren(span),
+            in_token: None,
+            path: Box::new(Ident::new("crate", span).into()),
+        }),
+    })
+}
+
+fn ty(u: &mut Unstructured<'_>, depth: u32) -> Result<Type> {
+    let span = Span::call_site();
+    let max = if depth == 0 { 0 } else { 3 };
+    Ok(match u.int_in_range(0..=max)? {
+        0 => Type::Path(TypePath { qself: None, path: ident(u)?.into() }),
+        1 => Type::Reference(TypeReference {
+            and_token: Token![&](span),
+            lifetime: None,
+            mutability: u.arbitrary::<bool>()?.then(|| Token![mut](span)),
+            elem: Box::new(ty(u, depth - 1)?),
+        }),
+        2 => {
+            let mut elems = Punctuated::new();
+            for _ in 0..u.int_in_range(0..=3)? {
+                elems.push(ty(u, depth - 1)?);
+            }
+            let one = elems.len() == 1;
+            trailing_comma(u, &mut elems, one)?;
+            Type::Tuple(TypeTuple { paren_token: token::Paren(span), elems })
+        }
+        _ => Type::Slice(TypeSlice {
+            bracket_token: token::Bracket(span),
+            elem: Box::new(ty(u, depth - 1)?),
+        }),
+    })
+}
+
+fn trailing_comma<T>(
+    u: &mut Unstructured<'_>,
+    elems: &mut Punctuated<T, Token![,]>,
+    required: bool,
+) -> Result<()> {
+    if !elems.empty_or_trailing() && (required || u.arbitrary()?) {
+        elems.push_punct(Token![,](Span::call_site()));
+    }
+    Ok(())
+}
+
+// Identifiers and literals.
+fn expr_tokens(u: &mut Unstructured<'_>) -> Result<TokenStream> {
+    Ok(if u.arbitrary()? { ident(u)?.into_token_stream() } else { lit(u)?.into_token_stream() })
+}
+
+// -----------------------------------------------------------------------------
+// Function
+
+fn item_fn(u: &mut Unstructured<'_>, depth: u32) -> Result<ItemFn> {
+    Ok(ItemFn {
+        attrs: attrs(u, false)?,
+        vis: visibility(u)?,
+        sig: signature(u, depth)?,
+        block: Box::new(block(u, depth)?),
+    })
+}
+
+fn block(u: &mut Unstructured<'_>, depth: u32) -> Result<Block> {
+    let mut stmts = TokenStream::new();
+    if depth > 0 {
+        let len = u.int_in_range(0..=4)?;
+        for i in 0..len {
+            stmt(u, depth - 1, i + 1 == len)?.to_tokens(&mut stmts);
+        }
+    }
+    Ok(Block { brace_token: token::Brace(Span::call_site()), inner_attrs: attrs(u, true)?, stmts })
+}
+
+fn signature(u: &mut Unstructured<'_>, depth: u32) -> Result<Signature> {
+    let span = Span::call_site();
+
+    let mut generics = Generics::default();
+    for _ in 0..u.int_in_range(0..=1)? {
+        generics.params.push(GenericParam::Lifetime(LifetimeParam::new(lifetime(u)?)));
+    }
+    for _ in 0..u.int_in_range(0..=2)? {
+        generics.params.push(GenericParam::Type(TypeParam::from(ident(u)?)));
+    }
+    if !generics.params.is_empty() {
+        generics.lt_token = Some(Token![<](span));
+        generics.gt_token = Some(Token![>](span));
+    }
+
+    // The receiver can only be the first argument.
+    let mut inputs = Punctuated::new();
+    if u.arbitrary()? {
+        inputs.push(fn_arg_receiver(u, depth)?);
+    }
+    for _ in 0..u.int_in_range(0..=3)? {
+        inputs.push(FnArg::Typed(PatType { attrs: attrs(u, false)?, ..pat_type(u, depth)? }));
+    }
+    let variadic = if u.ratio(1, 4)? { Some(variadic(u, depth)?) } else { None };
+    // The comma before the variadic argument is printed even if it is not in
+    // `inputs`.
+    trailing_comma(u, &mut inputs, variadic.is_some())?;
+
+    Ok(Signature {
+        constness: u.arbitrary::<bool>()?.then(|| Token![const](span)),
+        asyncness: u.arbitrary::<bool>()?.then(|| Token![async](span)),
+        unsafety: u.arbitrary::<bool>()?.then(|| Token![unsafe](span)),
+        abi: if u.arbitrary()? {
+            Some(Abi {
+                extern_token: Token![extern](span),
+                name: u.arbitrary::<bool>()?.then(|| LitStr::new("C", span)),
+            })
+        } else {
+            None
+        },
+        fn_token: Token![fn](span),
+        ident: ident(u)?,
+        generics,
+        paren_token: token::Paren(span),
+        inputs,
+        variadic,
+        output: if u.arbitrary()? {
+            ReturnType::Type(Token![->](span), Box::new(ty(u, depth)?))
+        } else {
+            ReturnType::Default
+        },
+    })
+}
+
+fn fn_arg(u: &mut Unstructured<'_>, depth: u32) -> Result<FnArg> {
+    if u.arbitrary()? {
+        fn_arg_receiver(u, depth)
+    } else {
+        Ok(FnArg::Typed(PatType { attrs: attrs(u, false)?, ..pat_type(u, depth)? }))
+    }
+}
+
+// `Receiver::parse` does not parse attributes, but `FnArg::parse` does.
+fn fn_arg_receiver(u: &mut Unstructured<'_>, depth: u32) -> Result<FnArg> {
+    Ok(FnArg::Receiver(Receiver { attrs: attrs(u, false)?, ..receiver(u, depth)? }))
+}
+
+fn receiver(u: &mut Unstructured<'_>, depth: u32) -> Result<Receiver> {
+    let span = Span::call_site();
+    let self_ty = Type::Path(TypePath { qself: None, path: Ident::new("Self", span).into() });
+    let mutability = u.arbitrary::<bool>()?.then(|| Token![mut](span));
+    let (reference, colon_token, ty) = match u.int_in_range(0..=2)? {
+        // `self`
+        0 => (None, None, self_ty),
+        // `&'a self`
+        1 => {
+            let lifetime = if u.arbitrary()? { Some(lifetime(u)?) } else { None };
+            let ty = Type::Reference(TypeReference {
+                and_token: Token![&](span),
+                lifetime: lifetime.clone(),
+                mutability: mutability.as_ref().map(|_| Token![mut](span)),
+                elem: Box::new(self_ty),
+            });
+            (Some((Token![&](span), lifetime)), None, ty)
+        }
+        // `self: Type`
+        _ => (None, Some(Token![:](span)), ty(u, depth)?),
+    };
+    Ok(Receiver {
+        attrs: vec![],
+        reference,
+        mutability,
+        self_token: Token![self](span),
+        colon_token,
+        ty: Box::new(ty),
+    })
+}
+
+fn variadic(u: &mut Unstructured<'_>, depth: u32) -> Result<Variadic> {
+    let span = Span::call_site();
+    Ok(Variadic {
+        attrs: attrs(u, false)?,
+        pat: if u.arbitrary()? {
+            Some((Box::new(pat(u, depth, false, false)?), Token![:](span)))
+        } else {
+            None
+        },
+        dots: Token![...](span),
+        comma: u.arbitrary::<bool>()?.then(|| Token![,](span)),
+    })
+}
+
+// -----------------------------------------------------------------------------
+// Pattern
+
+// `or` and `rest` are whether `|` and `..` patterns are allowed at the top
+// level.
+fn pat(u: &mut Unstructured<'_>, depth: u32, or: bool, rest: bool) -> Result<Pat> {
+    if or && depth > 0 && u.ratio(1, 4)? {
+        return pat_or(u, depth).map(Pat::Or);
+    }
+    if rest && u.ratio(1, 4)? {
+        return Ok(Pat::Rest(pat_rest()));
+    }
+    let max = if depth == 0 { 3 } else { 7 };
+    Ok(match u.int_in_range(0..=max)? {
+        0 => Pat::Ident(pat_ident(u)?),
+        1 => Pat::Lit(lit(u)?),
+        // A path with one segment is parsed as an identifier pattern.
+        2 => Pat::Path(ExprPath { attrs: vec![], qself: None, path: path(u, 2)? }),
+        3 => Pat::Wild(pat_wild()),
+        4 => Pat::Reference(pat_reference(u, depth)?),
+        5 => Pat::Struct(pat_struct(u, depth)?),
+        6 => Pat::Tuple(pat_tuple(u, depth)?),
+        _ => Pat::TupleStruct(pat_tuple_struct(u, depth)?),
+    })
+}
+
+fn pat_ident(u: &mut Unstructured<'_>) -> Result<PatIdent> {
+    let span = Span::call_site();
+    Ok(PatIdent {
+        attrs: vec![],
+        by_ref: u.arbitrary::<bool>()?.then(|| Token![ref](span)),
+        mutability: u.arbitrary::<bool>()?.then(|| Token![mut](span)),
+        ident: ident(u)?,
+    })
+}
+
+fn pat_or(u: &mut Unstructured<'_>, depth: u32) -> Result<PatOr> {
+    let mut cases = Punctuated::new();
+    for _ in 0..u.int_in_range(2..=3)? {
+        cases.push(pat(u, depth.saturating_sub(1), false, false)?);
+    }
+    Ok(PatOr {
+        attrs: vec![],
+        leading_vert: u.arbitrary::<bool>()?.then(|| Token![|](Span::call_site())),
+        cases,
+    })
+}
+
+fn pat_reference(u: &mut Unstructured<'_>, depth: u32) -> Result<PatReference> {
+    let span = Span::call_site();
+    let mutability = u.arbitrary::<bool>()?.then(|| Token![mut](span));
+    let mut pat = pat(u, depth.saturating_sub(1), false, false)?;
+    // `& mut x` is parsed as `&mut x`.
+    if let (None, Pat::Ident(pat)) = (&mutability, &mut pat) {
+        if pat.by_ref.is_none() {
+            pat.mutability = None;
+        }
+    }
+    Ok(PatReference { attrs: vec![], and_token: Token![&](span), mutability, pat: Box::new(pat) })
+}
+
+fn pat_rest() -> PatRest {
+    PatRest { attrs: vec![], dot2_token: Token![..](Span::call_site()) }
+}
+
+fn pat_struct(u: &mut Unstructured<'_>, depth: u32) -> Result<PatStruct> {
+    let path = path(u, 1)?;
+    let mut fields = Punctuated::new();
+    for _ in 0..u.int_in_range(0..=3)? {
+        fields.push(field_pat(u, depth)?);
+    }
+    let rest =
+        if u.arbitrary()? { Some(PatRest { attrs: attrs(u, false)?, ..pat_rest() }) } else { None };
+    // The comma before `..` is printed even if it is not in `fields`.
+    trailing_comma(u, &mut fields, rest.is_some())?;
+    Ok(PatStruct {
+        attrs: vec![],
+        path,
+        brace_token: token::Brace(Span::call_site()),
+        fields,
+        rest,
+    })
+}
+
+fn pat_tuple(u: &mut Unstructured<'_>, depth: u32) -> Result<PatTuple> {
+    let mut elems = tuple_elems(u, depth)?;
+    // `(a)` is a parenthesized pattern, not a tuple.
+    let one = elems.len() == 1;
+    trailing_comma(u, &mut elems, one)?;
+    Ok(PatTuple { attrs: vec![], paren_token: token::Paren(Span::call_site()), elems })
+}
+
+fn pat_tuple_struct(u: &mut Unstructured<'_>, depth: u32) -> Result<PatTupleStruct> {
+    let path = path(u, 1)?;
+    let mut elems = tuple_elems(u, depth)?;
+    trailing_comma(u, &mut elems, false)?;
+    Ok(PatTupleStruct { attrs: vec![], path, paren_token: token::Paren(Span::call_site()), elems })
+}
+
+// At most one `..` is allowed in a tuple.
+fn tuple_elems(u: &mut Unstructured<'_>, depth: u32) -> Result<Punctuated<Pat, Token![,]>> {
+    let mut elems = Punctuated::new();
+    let mut rest = true;
+    for _ in 0..u.int_in_range(0..=3)? {
+        let pat = pat(u, depth.saturating_sub(1), true, rest)?;
+        rest &= !matches!(pat, Pat::Rest(_));
+        elems.push(pat);
+    }
+    Ok(elems)
+}
+
+fn pat_type(u: &mut Unstructured<'_>, depth: u32) -> Result<PatType> {
+    Ok(PatType {
+        attrs: vec![],
+        pat: Box::new(pat(u, depth, false, false)?),
+        colon_token: Token![:](Span::call_site()),
+        ty: Box::new(ty(u, depth)?),
+    })
+}
+
+fn pat_wild() -> PatWild {
+    PatWild { attrs: vec![], underscore_token: Token![_](Span::call_site()) }
+}
+
+fn field_pat(u: &mut Unstructured<'_>, depth: u32) -> Result<FieldPat> {
+    let attrs = attrs(u, false)?;
+    if u.arbitrary()? {
+        // Shorthand: `ref mut x`
+        let pat = pat_ident(u)?;
+        return Ok(FieldPat {
+            attrs,
+            member: Member::Named(pat.ident.clone()),
+            colon_token: None,
+            pat: Box::new(Pat::Ident(pat)),
+        });
+    }
+    let member = if u.arbitrary()? {
+        Member::Named(ident(u)?)
+    } else {
+        Member::Unnamed(Index { index: u.int_in_range(0..=3)?, span: Span::call_site() })
+    };
+    Ok(FieldPat {
+        attrs,
+        member,
+        colon_token: Some(Token![:](Span::call_site())),
+        pat: Box::new(pat(u, depth.saturating_sub(1), true, false)?),
+    })
+}
+
+// -----------------------------------------------------------------------------
+// Expression
+
+fn expr_match(u: &mut Unstructured<'_>, depth: u32) -> Result<ExprMatch> {
+    let mut arms = vec![];
+    if depth > 0 {
+        let len = u.int_in_range(0..=3)?;
+        for i in 0..len {
+            arms.push(arm(u, depth - 1, i + 1 == len)?);
+        }
+    }
+    Ok(ExprMatch {
+        attrs: attrs(u, false)?,
+        match_token: Token![match](Span::call_site()),
+        expr: expr_tokens(u)?,
+        brace_token: token::Brace(Span::call_site()),
+        arms,
+    })
+}
+
+// The comma is required after a body that is not a block, unless it is the
+// last arm.
+fn arm(u: &mut Unstructured<'_>, depth: u32, last: bool) -> Result<Arm> {
+    let span = Span::call_site();
+    let (body, requires_comma) = if u.arbitrary()? {
+        (block(u, depth)?.into_token_stream(), false)
+    } else {
+        (expr_tokens(u)?, !last)
+    };
+    Ok(Arm {
+        attrs: attrs(u, false)?,
+        pat: pat(u, depth, true, false)?,
+        guard: if u.arbitrary()? { Some((Token![if](span), expr_tokens(u)?)) } else { None },
+        fat_arrow_token: Token![=>](span),
+        body,
+        comma: (requires_comma || u.arbitrary()?).then(|| Token![,](span)),
+    })
+}
+
+// -----------------------------------------------------------------------------
+// Statement
+
+// The semicolon is required after an expression that is not block-like,
+// unless it is the trailing expression of a block.
+fn stmt(u: &mut Unstructured<'_>, depth: u32, allow_nosemi: bool) -> Result<Stmt> {
+    let span = Span::call_site();
+    let max = if depth == 0 { 2 } else { 4 };
+    Ok(match u.int_in_range(0..=max)? {
+        0 => {
+            Stmt::Expr(expr_tokens(u)?, (!allow_nosemi || u.arbitrary()?).then(|| Token![;](span)))
+        }
+        1 => Stmt::Macro(stmt_macro(u)?),
+        2 => Stmt::Local(local(u, depth)?),
+        3 => Stmt::Expr(
+            expr_match(u, depth)?.into_token_stream(),
+            u.arbitrary::<bool>()?.then(|| Token![;](span)),
+        ),
+        _ => Stmt::Item(item_fn(u, depth - 1)?.into_token_stream()),
+    })
+}
+
+fn local(u: &mut Unstructured<'_>, depth: u32) -> Result<Local> {
+    let span = Span::call_site();
+    let mut pat = pat(u, depth, false, false)?;
+    if u.arbitrary()? {
+        pat = Pat::Type(PatType {
+            attrs: vec![],
+            pat: Box::new(pat),
+            colon_token: Token![:](span),
+            ty: Box::new(ty(u, depth)?),
+        });
+    }
+    Ok(Local {
+        attrs: attrs(u, false)?,
+        let_token: Token![let](span),
+        pat,
+        init: if u.arbitrary()? { Some(local_init(u, depth)?) } else { None },
+        semi_token: Token![;](span),
+    })
+}
+
+fn local_init(u: &mut Unstructured<'_>, depth: u32) -> Result<LocalInit> {
+    let span = Span::call_site();
+    Ok(LocalInit {
+        eq_token: Token![=](span),
+        expr: expr_tokens(u)?,
+        diverge: if u.arbitrary()? {
+            Some((Token![else](span), block(u, depth.saturating_sub(1))?))
+        } else {
+            None
+        },
+    })
+}
+
+fn stmt_macro(u: &mut Unstructured<'_>) -> Result<StmtMacro> {
+    let span = Span::call_site();
+    let delimiter = match u.int_in_range(0..=2)? {
+        0 => MacroDelimiter::Paren(token::Paren(span)),
+        1 => MacroDelimiter::Bracket(token::Bracket(span)),
+        _ => MacroDelimiter::Brace(token::Brace(span)),
+    };
+    // Paren and bracket macros without a semicolon are expressions.
+    let requires_semi = !matches!(delimiter, MacroDelimiter::Brace(_));
+    Ok(StmtMacro {
+        attrs: attrs(u, false)?,
+        mac: Macro {
+            path: path(u, 1)?,
+            bang_token: Token![!](span),
+            delimiter,
+            tokens: if u.arbitrary()? { expr_tokens(u)? } else { TokenStream::new() },
+        },
+        semi_token: (requires_semi || u.arbitrary()?).then(|| Token![;](span)),
+    })
+}
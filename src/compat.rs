@@ -0,0 +1,55 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Function and pattern types from this crate and from syn under the same
+//! names.
+//!
+//! [`mid`] re-exports the data structures of this crate, and [`full`] (with
+//! the **`syn-full`** feature) re-exports the data structures of the same
+//! name from syn with its "full" feature. A macro that only uses the types
+//! through one of these modules can switch between the cheap and the full
+//! parser by changing which module it imports, for example with a feature of
+//! its own:
+//!
+#![cfg_attr(not(all(feature = "parsing", feature = "printing")), doc = "```ignore")]
+#![cfg_attr(all(feature = "parsing", feature = "printing"), doc = "```")]
+//! #[cfg(not(feature = "full"))]
+//! use syn_mid::compat::mid as ast;
+//! #[cfg(feature = "full")]
+//! use syn_mid::compat::full as ast;
+//!
+//! let item: ast::ItemFn = syn::parse_quote!(fn f(x: u8) -> u8 { x });
+//! assert_eq!(item.sig.ident, "f");
+//! ```
+//!
+//! The field names are the same, but the types of some of them are not:
+//!
+//! - [`Block::stmts`] is a [`TokenStream`](proc_macro2::TokenStream) in this
+//!   crate and a `Vec<syn::Stmt>` in syn.
+//! - The inner attributes of a function body are in [`Block::inner_attrs`] in
+//!   this crate and in `syn::ItemFn::attrs` in syn. `syn::Block` has no inner
+//!   attributes.
+//! - `syn::PatIdent` has a `subpat` field, and `syn::PatStruct` and
+//!   `syn::PatTupleStruct` have a `qself` field.
+//! - `syn::Pat` has more variants.
+//!
+//! [`Block::stmts`]: crate::Block::stmts
+//! [`Block::inner_attrs`]: crate::Block::inner_attrs
+
+/// The data structures of this crate.
+pub mod mid {
+    pub use crate::{
+        Block, FieldPat, FnArg, ItemFn, Pat, PatIdent, PatLit, PatOr, PatPath, PatReference,
+        PatRest, PatStruct, PatTuple, PatTupleStruct, PatType, PatWild, Receiver, Signature,
+        Variadic,
+    };
+}
+
+/// The data structures of syn with its "full" feature.
+#[cfg(feature = "syn-full")]
+pub mod full {
+    pub use syn::{
+        Block, FieldPat, FnArg, ItemFn, Pat, PatIdent, PatLit, PatOr, PatPath, PatReference,
+        PatRest, PatStruct, PatTuple, PatTupleStruct, PatType, PatWild, Receiver, Signature,
+        Variadic,
+    };
+}
@@ -0,0 +1,507 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Conversions between the data structures of this crate and those of syn with
+// "full" feature.
+//
+// The data structures are converted field by field. The only exception is the
+// body of a function, which syn-mid keeps as tokens and syn parses into
+// statements.
+
+use alloc::{boxed::Box, vec::Vec};
+
+use proc_macro2::TokenStream;
+use quote::{TokenStreamExt as _, quote};
+use syn::{
+    AttrStyle, Error,
+    parse::{Parser as _, Result},
+    punctuated::{Pair, Punctuated},
+};
+
+use crate::{
+    Block, FieldPat, FnArg, ItemFn, Pat, PatIdent, PatOr, PatReference, PatRest, PatStruct,
+    PatTuple, PatTupleStruct, PatType, PatWild, Receiver, Signature, Variadic,
+};
+
+fn map_punctuated<T, U, P>(punctuated: Punctuated<T, P>) -> Punctuated<U, P>
+where
+    U: From<T>,
+{
+    punctuated
+        .into_pairs()
+        .map(|pair| {
+            let (value, punct) = pair.into_tuple();
+            Pair::new(U::from(value), punct)
+        })
+        .collect()
+}
+
+fn try_map_punctuated<T, U, P>(punctuated: Punctuated<T, P>) -> Result<Punctuated<U, P>>
+where
+    U: TryFrom<T, Error = Error>,
+{
+    punctuated
+        .into_pairs()
+        .map(|pair| {
+            let (value, punct) = pair.into_tuple();
+            Ok(Pair::new(U::try_from(value)?, punct))
+        })
+        .collect()
+}
+
+fn try_into_box<T, U>(value: T) -> Result<Box<U>>
+where
+    U: TryFrom<T, Error = Error>,
+{
+    U::try_from(value).map(Box::new)
+}
+
+// -----------------------------------------------------------------------------
+// syn-mid -> syn
+
+impl TryFrom<ItemFn> for syn::ItemFn {
+    type Error = Error;
+
+    /// Converts a function, parsing its body.
+    ///
+    /// The inner attributes of the body are moved to [`syn::ItemFn::attrs`],
+    /// where syn keeps them.
+    fn try_from(item: ItemFn) -> Result<Self> {
+        let ItemFn { mut attrs, vis, sig, block } = item;
+        let Block { brace_token, inner_attrs, stmts } = *block;
+        attrs.extend(inner_attrs);
+        let stmts = syn::Block::parse_within.parse2(stmts)?;
+        Ok(Self { attrs, vis, sig: sig.into(), block: Box::new(syn::Block { brace_token, stmts }) })
+    }
+}
+
+impl TryFrom<Block> for syn::Block {
+    type Error = Error;
+
+    /// Converts a block, parsing its statements.
+    ///
+    /// Returns an error if the block has inner attributes, which
+    /// [`syn::Block`] cannot hold.
+    fn try_from(block: Block) -> Result<Self> {
+        if let Some(attr) = block.inner_attrs.first() {
+            return Err(Error::new_spanned(
+                attr,
+                "inner attributes cannot be converted to syn::Block",
+            ));
+        }
+        let stmts = syn::Block::parse_within.parse2(block.stmts)?;
+        Ok(Self { brace_token: block.brace_token, stmts })
+    }
+}
+
+impl From<Signature> for syn::Signature {
+    fn from(sig: Signature) -> Self {
+        Self {
+            constness: sig.constness,
+            asyncness: sig.asyncness,
+            unsafety: sig.unsafety,
+            abi: sig.abi,
+            fn_token: sig.fn_token,
+            ident: sig.ident,
+            generics: sig.generics,
+            paren_token: sig.paren_token,
+            inputs: map_punctuated(sig.inputs),
+            variadic: sig.variadic.map(Into::into),
+            output: sig.output,
+        }
+    }
+}
+
+impl From<FnArg> for syn::FnArg {
+    fn from(arg: FnArg) -> Self {
+        match arg {
+            FnArg::Receiver(receiver) => Self::Receiver(receiver.into()),
+            FnArg::Typed(arg) => Self::Typed(arg.into()),
+        }
+    }
+}
+
+impl From<Receiver> for syn::Receiver {
+    fn from(receiver: Receiver) -> Self {
+        Self {
+            attrs: receiver.attrs,
+            reference: receiver.reference,
+            mutability: receiver.mutability,
+            self_token: receiver.self_token,
+            colon_token: receiver.colon_token,
+            ty: receiver.ty,
+        }
+    }
+}
+
+impl From<Variadic> for syn::Variadic {
+    fn from(variadic: Variadic) -> Self {
+        Self {
+            attrs: variadic.attrs,
+            pat: variadic.pat.map(|(pat, colon_token)| (Box::new((*pat).into()), colon_token)),
+            dots: variadic.dots,
+            comma: variadic.comma,
+        }
+    }
+}
+
+impl From<Pat> for syn::Pat {
+    fn from(pat: Pat) -> Self {
+        match pat {
+            Pat::Ident(pat) => Self::Ident(pat.into()),
+            Pat::Lit(pat) => Self::Lit(pat),
+            Pat::Or(pat) => Self::Or(pat.into()),
+            Pat::Path(pat) => Self::Path(pat),
+            Pat::Reference(pat) => Self::Reference(pat.into()),
+            Pat::Rest(pat) => Self::Rest(pat.into()),
+            Pat::Struct(pat) => Self::Struct(pat.into()),
+            Pat::Tuple(pat) => Self::Tuple(pat.into()),
+            Pat::TupleStruct(pat) => Self::TupleStruct(pat.into()),
+            Pat::Type(pat) => Self::Type(pat.into()),
+            Pat::Verbatim(pat) => Self::Verbatim(pat),
+            Pat::Wild(pat) => Self::Wild(pat.into()),
+        }
+    }
+}
+
+impl From<PatIdent> for syn::PatIdent {
+    fn from(pat: PatIdent) -> Self {
+        Self {
+            attrs: pat.attrs,
+            by_ref: pat.by_ref,
+            mutability: pat.mutability,
+            ident: pat.ident,
+            subpat: None,
+        }
+    }
+}
+
+impl From<PatOr> for syn::PatOr {
+    fn from(pat: PatOr) -> Self {
+        Self { attrs: pat.attrs, leading_vert: pat.leading_vert, cases: map_punctuated(pat.cases) }
+    }
+}
+
+impl From<PatReference> for syn::PatReference {
+    fn from(pat: PatReference) -> Self {
+        Self {
+            attrs: pat.attrs,
+            and_token: pat.and_token,
+            mutability: pat.mutability,
+            pat: Box::new((*pat.pat).into()),
+        }
+    }
+}
+
+impl From<PatRest> for syn::PatRest {
+    fn from(pat: PatRest) -> Self {
+        Self { attrs: pat.attrs, dot2_token: pat.dot2_token }
+    }
+}
+
+impl From<PatStruct> for syn::PatStruct {
+    fn from(pat: PatStruct) -> Self {
+        Self {
+            attrs: pat.attrs,
+            qself: None,
+            path: pat.path,
+            brace_token: pat.brace_token,
+            fields: map_punctuated(pat.fields),
+            rest: pat.rest.map(Into::into),
+        }
+    }
+}
+
+impl From<PatTuple> for syn::PatTuple {
+    fn from(pat: PatTuple) -> Self {
+        Self { attrs: pat.attrs, paren_token: pat.paren_token, elems: map_punctuated(pat.elems) }
+    }
+}
+
+impl From<PatTupleStruct> for syn::PatTupleStruct {
+    fn from(pat: PatTupleStruct) -> Self {
+        Self {
+            attrs: pat.attrs,
+            qself: None,
+            path: pat.path,
+            paren_token: pat.paren_token,
+            elems: map_punctuated(pat.elems),
+        }
+    }
+}
+
+impl From<PatType> for syn::PatType {
+    fn from(pat: PatType) -> Self {
+        Self {
+            attrs: pat.attrs,
+            pat: Box::new((*pat.pat).into()),
+            colon_token: pat.colon_token,
+            ty: pat.ty,
+        }
+    }
+}
+
+impl From<PatWild> for syn::PatWild {
+    fn from(pat: PatWild) -> Self {
+        Self { attrs: pat.attrs, underscore_token: pat.underscore_token }
+    }
+}
+
+impl From<FieldPat> for syn::FieldPat {
+    fn from(field: FieldPat) -> Self {
+        Self {
+            attrs: field.attrs,
+            member: field.member,
+            colon_token: field.colon_token,
+            pat: Box::new((*field.pat).into()),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// syn -> syn-mid
+
+impl TryFrom<syn::ItemFn> for ItemFn {
+    type Error = Error;
+
+    /// Converts a function, printing its body to tokens.
+    ///
+    /// The inner attributes in [`syn::ItemFn::attrs`] are moved to
+    /// [`Block::inner_attrs`].
+    fn try_from(item: syn::ItemFn) -> Result<Self> {
+        let (inner_attrs, attrs): (Vec<_>, Vec<_>) =
+            item.attrs.into_iter().partition(|attr| matches!(attr.style, AttrStyle::Inner(_)));
+        let mut block = Block::from(*item.block);
+        block.inner_attrs = inner_attrs;
+        Ok(Self { attrs, vis: item.vis, sig: item.sig.try_into()?, block: Box::new(block) })
+    }
+}
+
+impl From<syn::Block> for Block {
+    fn from(block: syn::Block) -> Self {
+        let mut stmts = TokenStream::new();
+        stmts.append_all(block.stmts);
+        Self { brace_token: block.brace_token, inner_attrs: Vec::new(), stmts }
+    }
+}
+
+impl TryFrom<syn::Signature> for Signature {
+    type Error = Error;
+
+    fn try_from(sig: syn::Signature) -> Result<Self> {
+        Ok(Self {
+            constness: sig.constness,
+            asyncness: sig.asyncness,
+            unsafety: sig.unsafety,
+            abi: sig.abi,
+            fn_token: sig.fn_token,
+            ident: sig.ident,
+            generics: sig.generics,
+            paren_token: sig.paren_token,
+            inputs: try_map_punctuated(sig.inputs)?,
+            variadic: sig.variadic.map(TryInto::try_into).transpose()?,
+            output: sig.output,
+        })
+    }
+}
+
+impl TryFrom<syn::FnArg> for FnArg {
+    type Error = Error;
+
+    fn try_from(arg: syn::FnArg) -> Result<Self> {
+        match arg {
+            syn::FnArg::Receiver(receiver) => Ok(Self::Receiver(receiver.into())),
+            syn::FnArg::Typed(arg) => Ok(Self::Typed(arg.try_into()?)),
+        }
+    }
+}
+
+impl From<syn::Receiver> for Receiver {
+    fn from(receiver: syn::Receiver) -> Self {
+        Self {
+            attrs: receiver.attrs,
+            reference: receiver.reference,
+            mutability: receiver.mutability,
+            self_token: receiver.self_token,
+            colon_token: receiver.colon_token,
+            ty: receiver.ty,
+        }
+    }
+}
+
+impl TryFrom<syn::Variadic> for Variadic {
+    type Error = Error;
+
+    fn try_from(variadic: syn::Variadic) -> Result<Self> {
+        Ok(Self {
+            attrs: variadic.attrs,
+            pat: match variadic.pat {
+                Some((pat, colon_token)) => Some((try_into_box(*pat)?, colon_token)),
+                None => None,
+            },
+            dots: variadic.dots,
+            comma: variadic.comma,
+        })
+    }
+}
+
+impl TryFrom<syn::Pat> for Pat {
+    type Error = Error;
+
+    /// Converts a pattern.
+    ///
+    /// Returns an error if the pattern, or one of its subpatterns, is of a
+    /// kind that syn-mid does not support, such as a range or slice pattern.
+    fn try_from(pat: syn::Pat) -> Result<Self> {
+        match pat {
+            syn::Pat::Ident(pat) => pat.try_into().map(Self::Ident),
+            syn::Pat::Lit(pat) => Ok(Self::Lit(pat)),
+            syn::Pat::Or(pat) => pat.try_into().map(Self::Or),
+            syn::Pat::Path(pat) => Ok(Self::Path(pat)),
+            syn::Pat::Reference(pat) => pat.try_into().map(Self::Reference),
+            syn::Pat::Rest(pat) => Ok(Self::Rest(pat.into())),
+            syn::Pat::Struct(pat) => pat.try_into().map(Self::Struct),
+            syn::Pat::Tuple(pat) => pat.try_into().map(Self::Tuple),
+            syn::Pat::TupleStruct(pat) => pat.try_into().map(Self::TupleStruct),
+            syn::Pat::Type(pat) => pat.try_into().map(Self::Type),
+            syn::Pat::Verbatim(pat) => Ok(Self::Verbatim(pat)),
+            syn::Pat::Wild(pat) => Ok(Self::Wild(pat.into())),
+            pat => Err(Error::new_spanned(pat, "unsupported pattern")),
+        }
+    }
+}
+
+impl TryFrom<syn::PatIdent> for PatIdent {
+    type Error = Error;
+
+    /// Converts an identifier pattern.
+    ///
+    /// Returns an error if the pattern has a subpattern: `x @ Some(_)`.
+    fn try_from(pat: syn::PatIdent) -> Result<Self> {
+        if let Some((at, subpat)) = &pat.subpat {
+            return Err(Error::new_spanned(
+                quote!(#at #subpat),
+                "unsupported pattern: subpattern binding",
+            ));
+        }
+        Ok(Self {
+            attrs: pat.attrs,
+            by_ref: pat.by_ref,
+            mutability: pat.mutability,
+            ident: pat.ident,
+        })
+    }
+}
+
+impl TryFrom<syn::PatOr> for PatOr {
+    type Error = Error;
+
+    fn try_from(pat: syn::PatOr) -> Result<Self> {
+        Ok(Self {
+            attrs: pat.attrs,
+            leading_vert: pat.leading_vert,
+            cases: try_map_punctuated(pat.cases)?,
+        })
+    }
+}
+
+impl TryFrom<syn::PatReference> for PatReference {
+    type Error = Error;
+
+    fn try_from(pat: syn::PatReference) -> Result<Self> {
+        Ok(Self {
+            attrs: pat.attrs,
+            and_token: pat.and_token,
+            mutability: pat.mutability,
+            pat: try_into_box(*pat.pat)?,
+        })
+    }
+}
+
+impl From<syn::PatRest> for PatRest {
+    fn from(pat: syn::PatRest) -> Self {
+        Self { attrs: pat.attrs, dot2_token: pat.dot2_token }
+    }
+}
+
+impl TryFrom<syn::PatStruct> for PatStruct {
+    type Error = Error;
+
+    /// Converts a struct pattern.
+    ///
+    /// Returns an error if the path is qualified: `<T as Trait>::Assoc { .. }`.
+    fn try_from(pat: syn::PatStruct) -> Result<Self> {
+        if pat.qself.is_some() {
+            return Err(Error::new_spanned(&pat, "unsupported pattern: qualified path"));
+        }
+        Ok(Self {
+            attrs: pat.attrs,
+            path: pat.path,
+            brace_token: pat.brace_token,
+            fields: try_map_punctuated(pat.fields)?,
+            rest: pat.rest.map(Into::into),
+        })
+    }
+}
+
+impl TryFrom<syn::PatTuple> for PatTuple {
+    type Error = Error;
+
+    fn try_from(pat: syn::PatTuple) -> Result<Self> {
+        Ok(Self {
+            attrs: pat.attrs,
+            paren_token: pat.paren_token,
+            elems: try_map_punctuated(pat.elems)?,
+        })
+    }
+}
+
+impl TryFrom<syn::PatTupleStruct> for PatTupleStruct {
+    type Error = Error;
+
+    /// Converts a tuple struct pattern.
+    ///
+    /// Returns an error if the path is qualified: `<T as Trait>::Assoc(..)`.
+    fn try_from(pat: syn::PatTupleStruct) -> Result<Self> {
+        if pat.qself.is_some() {
+            return Err(Error::new_spanned(&pat, "unsupported pattern: qualified path"));
+        }
+        Ok(Self {
+            attrs: pat.attrs,
+            path: pat.path,
+            paren_token: pat.paren_token,
+            elems: try_map_punctuated(pat.elems)?,
+        })
+    }
+}
+
+impl TryFrom<syn::PatType> for PatType {
+    type Error = Error;
+
+    fn try_from(pat: syn::PatType) -> Result<Self> {
+        Ok(Self {
+            attrs: pat.attrs,
+            pat: try_into_box(*pat.pat)?,
+            colon_token: pat.colon_token,
+            ty: pat.ty,
+        })
+    }
+}
+
+impl From<syn::PatWild> for PatWild {
+    fn from(pat: syn::PatWild) -> Self {
+        Self { attrs: pat.attrs, underscore_token: pat.underscore_token }
+    }
+}
+
+impl TryFrom<syn::FieldPat> for FieldPat {
+    type Error = Error;
+
+    fn try_from(field: syn::FieldPat) -> Result<Self> {
+        Ok(Self {
+            attrs: field.attrs,
+            member: field.member,
+            colon_token: field.colon_token,
+            pat: try_into_box(*field.pat)?,
+        })
+    }
+}
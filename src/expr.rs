@@ -0,0 +1,237 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Based on https://github.com/dtolnay/syn/blob/2.0.37/src/expr.rs.
+
+use alloc::vec::Vec;
+#[cfg(feature = "extra-traits")]
+use core::hash::{Hash, Hasher};
+
+use proc_macro2::TokenStream;
+use syn::{Attribute, Token, token};
+
+use super::Pat;
+#[cfg(feature = "extra-traits")]
+use crate::tt::TokenStreamHelper;
+
+ast_struct! {
+    /// A `match` expression whose scrutinee and arm bodies are not parsed:
+    /// `match n { Some(n) => {}, None => {} }`.
+    pub struct ExprMatch #manual_extra_traits {
+        pub attrs: Vec<Attribute>,
+        pub match_token: Token![match],
+        /// The scrutinee, not parsed.
+        pub expr: TokenStream,
+        pub brace_token: token::Brace,
+        pub arms: Vec<Arm>,
+    }
+}
+
+ast_struct! {
+    /// One arm of a `match` expression: `0..=10 => { return true; }`.
+    ///
+    /// As in syn, the comma after an arm is optional if the body is a block.
+    pub struct Arm #manual_extra_traits {
+        pub attrs: Vec<Attribute>,
+        /// The pattern of the arm. Patterns that this crate does not support,
+        /// such as `0..=10`, are kept as [`Pat::Verbatim`].
+        pub pat: Pat,
+        /// The guard of the arm, not parsed: `if x > 0`.
+        pub guard: Option<(Token![if], TokenStream)>,
+        pub fat_arrow_token: Token![=>],
+        /// The body of the arm, not parsed.
+        pub body: TokenStream,
+        pub comma: Option<Token![,]>,
+    }
+}
+
+#[cfg(feature = "extra-traits")]
+impl Eq for ExprMatch {}
+
+#[cfg(feature = "extra-traits")]
+impl PartialEq for ExprMatch {
+    fn eq(&self, other: &Self) -> bool {
+        self.attrs == other.attrs
+            && TokenStreamHelper(&self.expr) == TokenStreamHelper(&other.expr)
+            && self.arms == other.arms
+    }
+}
+
+#[cfg(feature = "extra-traits")]
+impl Hash for ExprMatch {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.attrs.hash(state);
+        TokenStreamHelper(&self.expr).hash(state);
+        self.arms.hash(state);
+    }
+}
+
+#[cfg(feature = "extra-traits")]
+impl Eq for Arm {}
+
+#[cfg(feature = "extra-traits")]
+impl PartialEq for Arm {
+    fn eq(&self, other: &Self) -> bool {
+        self.attrs == other.attrs
+            && self.pat == other.pat
+            && self.guard.as_ref().map(|(_, guard)| TokenStreamHelper(guard))
+                == other.guard.as_ref().map(|(_, guard)| TokenStreamHelper(guard))
+            && TokenStreamHelper(&self.body) == TokenStreamHelper(&other.body)
+            && self.comma == other.comma
+    }
+}
+
+#[cfg(feature = "extra-traits")]
+impl Hash for Arm {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.attrs.hash(state);
+        self.pat.hash(state);
+        self.guard.as_ref().map(|(_, guard)| TokenStreamHelper(guard)).hash(state);
+        TokenStreamHelper(&self.body).hash(state);
+        self.comma.hash(state);
+    }
+}
+
+#[cfg(feature = "parsing")]
+mod parsing {
+    use alloc::vec::Vec;
+
+    use proc_macro2::{TokenStream, TokenTree};
+    use syn::{
+        Attribute, Token, braced,
+        parse::{Parse, ParseStream, Parser as _, Result, discouraged::Speculative as _},
+    };
+
+    use super::{Arm, ExprMatch};
+    use crate::{
+        Block,
+        pat::{Pat, parsing::pat_or_verbatim},
+        stmt::parsing::{block_like, expr_tokens, is_block_like, until_brace},
+    };
+
+    impl Block {
+        /// Finds the `match` expressions at the top level of
+        /// [`stmts`](Self::stmts).
+        ///
+        /// A `match` is at the top level if it is not nested inside a
+        /// parenthesis, bracket, or brace, for example a statement
+        /// `match x { ... }`, an initializer `let y = match x { ... };`, or a
+        /// trailing `return match x { ... }`. `match` expressions in the
+        /// scrutinee or arms of another `match` are not returned separately.
+        pub fn top_level_matches(&self) -> Result<Vec<ExprMatch>> {
+            let find = |input: ParseStream<'_>| {
+                let mut matches = Vec::new();
+                while !input.is_empty() {
+                    let ahead = input.fork();
+                    ahead.call(Attribute::parse_outer)?;
+                    if ahead.peek(Token![match]) {
+                        matches.push(input.parse()?);
+                    } else {
+                        input.parse::<TokenTree>()?;
+                    }
+                }
+                Ok(matches)
+            };
+            find.parse2(self.stmts.clone())
+        }
+    }
+
+    impl Parse for ExprMatch {
+        fn parse(input: ParseStream<'_>) -> Result<Self> {
+            let attrs = input.call(Attribute::parse_outer)?;
+            let match_token: Token![match] = input.parse()?;
+            let mut expr = TokenStream::new();
+            until_brace(input, &mut expr, false)?;
+
+            let content;
+            let brace_token = braced!(content in input);
+
+            let mut arms = Vec::new();
+            while !content.is_empty() {
+                arms.push(content.call(Arm::parse)?);
+            }
+
+            Ok(Self { attrs, match_token, expr, brace_token, arms })
+        }
+    }
+
+    impl Parse for Arm {
+        fn parse(input: ParseStream<'_>) -> Result<Self> {
+            let attrs = input.call(Attribute::parse_outer)?;
+            let pat = pat_or_verbatim(input, Pat::parse_multi_with_leading_vert, |input| {
+                input.peek(Token![if]) || input.peek(Token![=>])
+            })?;
+            let guard = if input.peek(Token![if]) {
+                let if_token: Token![if] = input.parse()?;
+                let mut guard = TokenStream::new();
+                while !input.peek(Token![=>]) {
+                    guard.extend([input.parse::<TokenTree>()?]);
+                }
+                Some((if_token, guard))
+            } else {
+                None
+            };
+            let fat_arrow_token: Token![=>] = input.parse()?;
+
+            let mut body = TokenStream::new();
+            let requires_comma = arm_body(input, &mut body)?;
+            let comma = if requires_comma && !input.is_empty() {
+                Some(input.parse()?)
+            } else {
+                input.parse()?
+            };
+
+            Ok(Self { attrs, pat, guard, fat_arrow_token, body, comma })
+        }
+    }
+
+    /// Consumes the body of a match arm and returns whether it must be
+    /// followed by a comma.
+    fn arm_body(input: ParseStream<'_>, tokens: &mut TokenStream) -> Result<bool> {
+        if is_block_like(input)? {
+            let ahead = input.fork();
+            let mut block = TokenStream::new();
+            block_like(&ahead, &mut block)?;
+            if !(ahead.peek(Token![.]) && !ahead.peek(Token![..]) || ahead.peek(Token![?])) {
+                input.advance_to(&ahead);
+                tokens.extend(block);
+                return Ok(false);
+            }
+        }
+
+        expr_tokens(input, tokens)?;
+        Ok(true)
+    }
+}
+
+#[cfg(feature = "printing")]
+mod printing {
+    use proc_macro2::TokenStream;
+    use quote::{ToTokens, TokenStreamExt as _};
+
+    use super::{Arm, ExprMatch};
+
+    impl ToTokens for ExprMatch {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            tokens.append_all(&self.attrs);
+            self.match_token.to_tokens(tokens);
+            self.expr.to_tokens(tokens);
+            self.brace_token.surround(tokens, |tokens| {
+                tokens.append_all(&self.arms);
+            });
+        }
+    }
+
+    impl ToTokens for Arm {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            tokens.append_all(&self.attrs);
+            self.pat.to_tokens(tokens);
+            if let Some((if_token, guard)) = &self.guard {
+                if_token.to_tokens(tokens);
+                guard.to_tokens(tokens);
+            }
+            self.fat_arrow_token.to_tokens(tokens);
+            self.body.to_tokens(tokens);
+            self.comma.to_tokens(tokens);
+        }
+    }
+}
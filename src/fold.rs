@@ -0,0 +1,612 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Syntax tree traversal to transform the nodes of an owned syntax tree.
+//!
+//! Each method of the [`Fold`] trait is a hook that can be overridden to
+//! customize the behavior when transforming the corresponding type of node.
+//! By default, every method recursively visits the substructure of the input
+//! by invoking the right folder method of each of its fields, and rebuilds the
+//! node from the results.
+//!
+//! The types from syn that the data structures of this crate contain, such as
+//! [`Type`](syn::Type) and [`Generics`](syn::Generics), are traversed with
+//! [`syn::fold`], and the nodes of those types nested in them are passed back
+//! to the methods of this trait. Tokens that are not parsed, such as
+//! [`Block::stmts`](crate::Block::stmts), are kept as is.
+//!
+//! # Examples
+//!
+#![cfg_attr(not(all(feature = "parsing", feature = "printing")), doc = "```ignore")]
+#![cfg_attr(all(feature = "parsing", feature = "printing"), doc = "```")]
+//! use quote::quote;
+//! use syn_mid::{ItemFn, PatIdent, fold::{self, Fold}};
+//!
+//! // Removes `mut` from bindings.
+//! struct Immutable;
+//!
+//! impl Fold for Immutable {
+//!     fn fold_pat_ident(&mut self, node: PatIdent) -> PatIdent {
+//!         fold::fold_pat_ident(self, PatIdent { mutability: None, ..node })
+//!     }
+//! }
+//!
+//! let item: ItemFn = syn::parse_quote! {
+//!     fn f(mut x: u8, (mut y, z): (u8, u8)) {}
+//! };
+//! let item = Immutable.fold_item_fn(item);
+//! let sig = &item.sig;
+//! assert_eq!(quote!(#sig).to_string(), quote!(fn f(x: u8, (y, z): (u8, u8))).to_string());
+//! ```
+
+use alloc::{boxed::Box, vec::Vec};
+
+use syn::punctuated::{Pair, Punctuated};
+
+/// Syntax tree traversal to transform the nodes of an owned syntax tree.
+///
+/// See the [module documentation] for details.
+///
+/// [module documentation]: self
+pub trait Fold {
+    fn fold_abi(&mut self, i: syn::Abi) -> syn::Abi {
+        fold_abi(self, i)
+    }
+    fn fold_arm(&mut self, i: crate::Arm) -> crate::Arm {
+        fold_arm(self, i)
+    }
+    fn fold_attribute(&mut self, i: syn::Attribute) -> syn::Attribute {
+        fold_attribute(self, i)
+    }
+    fn fold_block(&mut self, i: crate::Block) -> crate::Block {
+        fold_block(self, i)
+    }
+    fn fold_expr_lit(&mut self, i: syn::ExprLit) -> syn::ExprLit {
+        fold_expr_lit(self, i)
+    }
+    fn fold_expr_match(&mut self, i: crate::ExprMatch) -> crate::ExprMatch {
+        fold_expr_match(self, i)
+    }
+    fn fold_expr_path(&mut self, i: syn::ExprPath) -> syn::ExprPath {
+        fold_expr_path(self, i)
+    }
+    fn fold_field_pat(&mut self, i: crate::FieldPat) -> crate::FieldPat {
+        fold_field_pat(self, i)
+    }
+    fn fold_fn_arg(&mut self, i: crate::FnArg) -> crate::FnArg {
+        fold_fn_arg(self, i)
+    }
+    fn fold_generics(&mut self, i: syn::Generics) -> syn::Generics {
+        fold_generics(self, i)
+    }
+    fn fold_ident(&mut self, i: syn::Ident) -> syn::Ident {
+        fold_ident(self, i)
+    }
+    fn fold_item_fn(&mut self, i: crate::ItemFn) -> crate::ItemFn {
+        fold_item_fn(self, i)
+    }
+    fn fold_lifetime(&mut self, i: syn::Lifetime) -> syn::Lifetime {
+        fold_lifetime(self, i)
+    }
+    fn fold_local(&mut self, i: crate::Local) -> crate::Local {
+        fold_local(self, i)
+    }
+    fn fold_local_init(&mut self, i: crate::LocalInit) -> crate::LocalInit {
+        fold_local_init(self, i)
+    }
+    fn fold_macro(&mut self, i: syn::Macro) -> syn::Macro {
+        fold_macro(self, i)
+    }
+    fn fold_member(&mut self, i: syn::Member) -> syn::Member {
+        fold_member(self, i)
+    }
+    fn fold_pat(&mut self, i: crate::Pat) -> crate::Pat {
+        fold_pat(self, i)
+    }
+    fn fold_pat_ident(&mut self, i: crate::PatIdent) -> crate::PatIdent {
+        fold_pat_ident(self, i)
+    }
+    fn fold_pat_or(&mut self, i: crate::PatOr) -> crate::PatOr {
+        fold_pat_or(self, i)
+    }
+    fn fold_pat_reference(&mut self, i: crate::PatReference) -> crate::PatReference {
+        fold_pat_reference(self, i)
+    }
+    fn fold_pat_rest(&mut self, i: crate::PatRest) -> crate::PatRest {
+        fold_pat_rest(self, i)
+    }
+    fn fold_pat_struct(&mut self, i: crate::PatStruct) -> crate::PatStruct {
+        fold_pat_struct(self, i)
+    }
+    fn fold_pat_tuple(&mut self, i: crate::PatTuple) -> crate::PatTuple {
+        fold_pat_tuple(self, i)
+    }
+    fn fold_pat_tuple_struct(&mut self, i: crate::PatTupleStruct) -> crate::PatTupleStruct {
+        fold_pat_tuple_struct(self, i)
+    }
+    fn fold_pat_type(&mut self, i: crate::PatType) -> crate::PatType {
+        fold_pat_type(self, i)
+    }
+    fn fold_pat_wild(&mut self, i: crate::PatWild) -> crate::PatWild {
+        fold_pat_wild(self, i)
+    }
+    fn fold_path(&mut self, i: syn::Path) -> syn::Path {
+        fold_path(self, i)
+    }
+    fn fold_receiver(&mut self, i: crate::Receiver) -> crate::Receiver {
+        fold_receiver(self, i)
+    }
+    fn fold_return_type(&mut self, i: syn::ReturnType) -> syn::ReturnType {
+        fold_return_type(self, i)
+    }
+    fn fold_signature(&mut self, i: crate::Signature) -> crate::Signature {
+        fold_signature(self, i)
+    }
+    fn fold_stmt(&mut self, i: crate::Stmt) -> crate::Stmt {
+        fold_stmt(self, i)
+    }
+    fn fold_stmt_macro(&mut self, i: crate::StmtMacro) -> crate::StmtMacro {
+        fold_stmt_macro(self, i)
+    }
+    fn fold_type(&mut self, i: syn::Type) -> syn::Type {
+        fold_type(self, i)
+    }
+    fn fold_variadic(&mut self, i: crate::Variadic) -> crate::Variadic {
+        fold_variadic(self, i)
+    }
+    fn fold_visibility(&mut self, i: syn::Visibility) -> syn::Visibility {
+        fold_visibility(self, i)
+    }
+}
+
+pub fn fold_abi<F>(f: &mut F, node: syn::Abi) -> syn::Abi
+where
+    F: Fold + ?Sized,
+{
+    syn::fold::fold_abi(&mut Syn(f), node)
+}
+
+pub fn fold_arm<F>(f: &mut F, node: crate::Arm) -> crate::Arm
+where
+    F: Fold + ?Sized,
+{
+    crate::Arm {
+        attrs: fold_vec(node.attrs, f, F::fold_attribute),
+        pat: f.fold_pat(node.pat),
+        guard: node.guard,
+        fat_arrow_token: node.fat_arrow_token,
+        body: node.body,
+        comma: node.comma,
+    }
+}
+
+pub fn fold_attribute<F>(f: &mut F, node: syn::Attribute) -> syn::Attribute
+where
+    F: Fold + ?Sized,
+{
+    syn::fold::fold_attribute(&mut Syn(f), node)
+}
+
+pub fn fold_block<F>(f: &mut F, node: crate::Block) -> crate::Block
+where
+    F: Fold + ?Sized,
+{
+    crate::Block {
+        brace_token: node.brace_token,
+        inner_attrs: fold_vec(node.inner_attrs, f, F::fold_attribute),
+        stmts: node.stmts,
+    }
+}
+
+pub fn fold_expr_lit<F>(f: &mut F, node: syn::ExprLit) -> syn::ExprLit
+where
+    F: Fold + ?Sized,
+{
+    syn::fold::fold_expr_lit(&mut Syn(f), node)
+}
+
+pub fn fold_expr_match<F>(f: &mut F, node: crate::ExprMatch) -> crate::ExprMatch
+where
+    F: Fold + ?Sized,
+{
+    crate::ExprMatch {
+        attrs: fold_vec(node.attrs, f, F::fold_attribute),
+        match_token: node.match_token,
+        expr: node.expr,
+        brace_token: node.brace_token,
+        arms: fold_vec(node.arms, f, F::fold_arm),
+    }
+}
+
+pub fn fold_expr_path<F>(f: &mut F, node: syn::ExprPath) -> syn::ExprPath
+where
+    F: Fold + ?Sized,
+{
+    syn::fold::fold_expr_path(&mut Syn(f), node)
+}
+
+pub fn fold_field_pat<F>(f: &mut F, node: crate::FieldPat) -> crate::FieldPat
+where
+    F: Fold + ?Sized,
+{
+    crate::FieldPat {
+        attrs: fold_vec(node.attrs, f, F::fold_attribute),
+        member: f.fold_member(node.member),
+        colon_token: node.colon_token,
+        pat: Box::new(f.fold_pat(*node.pat)),
+    }
+}
+
+pub fn fold_fn_arg<F>(f: &mut F, node: crate::FnArg) -> crate::FnArg
+where
+    F: Fold + ?Sized,
+{
+    match node {
+        crate::FnArg::Receiver(it) => crate::FnArg::Receiver(f.fold_receiver(it)),
+        crate::FnArg::Typed(it) => crate::FnArg::Typed(f.fold_pat_type(it)),
+    }
+}
+
+pub fn fold_generics<F>(f: &mut F, node: syn::Generics) -> syn::Generics
+where
+    F: Fold + ?Sized,
+{
+    syn::fold::fold_generics(&mut Syn(f), node)
+}
+
+pub fn fold_ident<F>(f: &mut F, node: syn::Ident) -> syn::Ident
+where
+    F: Fold + ?Sized,
+{
+    syn::fold::fold_ident(&mut Syn(f), node)
+}
+
+pub fn fold_item_fn<F>(f: &mut F, node: crate::ItemFn) -> crate::ItemFn
+where
+    F: Fold + ?Sized,
+{
+    crate::ItemFn {
+        attrs: fold_vec(node.attrs, f, F::fold_attribute),
+        vis: f.fold_visibility(node.vis),
+        sig: f.fold_signature(node.sig),
+        block: Box::new(f.fold_block(*node.block)),
+    }
+}
+
+pub fn fold_lifetime<F>(f: &mut F, node: syn::Lifetime) -> syn::Lifetime
+where
+    F: Fold + ?Sized,
+{
+    syn::fold::fold_lifetime(&mut Syn(f), node)
+}
+
+pub fn fold_local<F>(f: &mut F, node: crate::Local) -> crate::Local
+where
+    F: Fold + ?Sized,
+{
+    crate::Local {
+        attrs: fold_vec(node.attrs, f, F::fold_attribute),
+        let_token: node.let_token,
+        pat: f.fold_pat(node.pat),
+        init: node.init.map(|it| f.fold_local_init(it)),
+        semi_token: node.semi_token,
+    }
+}
+
+pub fn fold_local_init<F>(f: &mut F, node: crate::LocalInit) -> crate::LocalInit
+where
+    F: Fold + ?Sized,
+{
+    crate::LocalInit {
+        eq_token: node.eq_token,
+        expr: node.expr,
+        diverge: node.diverge.map(|(else_token, block)| (else_token, f.fold_block(block))),
+    }
+}
+
+pub fn fold_macro<F>(f: &mut F, node: syn::Macro) -> syn::Macro
+where
+    F: Fold + ?Sized,
+{
+    syn::fold::fold_macro(&mut Syn(f), node)
+}
+
+pub fn fold_member<F>(f: &mut F, node: syn::Member) -> syn::Member
+where
+    F: Fold + ?Sized,
+{
+    syn::fold::fold_member(&mut Syn(f), node)
+}
+
+pub fn fold_pat<F>(f: &mut F, node: crate::Pat) -> crate::Pat
+where
+    F: Fold + ?Sized,
+{
+    match node {
+        crate::Pat::Ident(it) => crate::Pat::Ident(f.fold_pat_ident(it)),
+        crate::Pat::Lit(it) => crate::Pat::Lit(f.fold_expr_lit(it)),
+        crate::Pat::Or(it) => crate::Pat::Or(f.fold_pat_or(it)),
+        crate::Pat::Path(it) => crate::Pat::Path(f.fold_expr_path(it)),
+        crate::Pat::Reference(it) => crate::Pat::Reference(f.fold_pat_reference(it)),
+        crate::Pat::Rest(it) => crate::Pat::Rest(f.fold_pat_rest(it)),
+        crate::Pat::Struct(it) => crate::Pat::Struct(f.fold_pat_struct(it)),
+        crate::Pat::Tuple(it) => crate::Pat::Tuple(f.fold_pat_tuple(it)),
+        crate::Pat::TupleStruct(it) => crate::Pat::TupleStruct(f.fold_pat_tuple_struct(it)),
+        crate::Pat::Type(it) => crate::Pat::Type(f.fold_pat_type(it)),
+        crate::Pat::Verbatim(it) => crate::Pat::Verbatim(it),
+        crate::Pat::Wild(it) => crate::Pat::Wild(f.fold_pat_wild(it)),
+    }
+}
+
+pub fn fold_pat_ident<F>(f: &mut F, node: crate::PatIdent) -> crate::PatIdent
+where
+    F: Fold + ?Sized,
+{
+    crate::PatIdent {
+        attrs: fold_vec(node.attrs, f, F::fold_attribute),
+        by_ref: node.by_ref,
+        mutability: node.mutability,
+        ident: f.fold_ident(node.ident),
+    }
+}
+
+pub fn fold_pat_or<F>(f: &mut F, node: crate::PatOr) -> crate::PatOr
+where
+    F: Fold + ?Sized,
+{
+    crate::PatOr {
+        attrs: fold_vec(node.attrs, f, F::fold_attribute),
+        leading_vert: node.leading_vert,
+        cases: fold_punctuated(node.cases, f, F::fold_pat),
+    }
+}
+
+pub fn fold_pat_reference<F>(f: &mut F, node: crate::PatReference) -> crate::PatReference
+where
+    F: Fold + ?Sized,
+{
+    crate::PatReference {
+        attrs: fold_vec(node.attrs, f, F::fold_attribute),
+        and_token: node.and_token,
+        mutability: node.mutability,
+        pat: Box::new(f.fold_pat(*node.pat)),
+    }
+}
+
+pub fn fold_pat_rest<F>(f: &mut F, node: crate::PatRest) -> crate::PatRest
+where
+    F: Fold + ?Sized,
+{
+    crate::PatRest {
+        attrs: fold_vec(node.attrs, f, F::fold_attribute),
+        dot2_token: node.dot2_token,
+    }
+}
+
+pub fn fold_pat_struct<F>(f: &mut F, node: crate::PatStruct) -> crate::PatStruct
+where
+    F: Fold + ?Sized,
+{
+    crate::PatStruct {
+        attrs: fold_vec(node.attrs, f, F::fold_attribute),
+        path: f.fold_path(node.path),
+        brace_token: node.brace_token,
+        fields: fold_punctuated(node.fields, f, F::fold_field_pat),
+        rest: node.rest.map(|it| f.fold_pat_rest(it)),
+    }
+}
+
+pub fn fold_pat_tuple<F>(f: &mut F, node: crate::PatTuple) -> crate::PatTuple
+where
+    F: Fold + ?Sized,
+{
+    crate::PatTuple {
+        attrs: fold_vec(node.attrs, f, F::fold_attribute),
+        paren_token: node.paren_token,
+        elems: fold_punctuated(node.elems, f, F::fold_pat),
+    }
+}
+
+pub fn fold_pat_tuple_struct<F>(f: &mut F, node: crate::PatTupleStruct) -> crate::PatTupleStruct
+where
+    F: Fold + ?Sized,
+{
+    crate::PatTupleStruct {
+        attrs: fold_vec(node.attrs, f, F::fold_attribute),
+        path: f.fold_path(node.path),
+        paren_token: node.paren_token,
+        elems: fold_punctuated(node.elems, f, F::fold_pat),
+    }
+}
+
+pub fn fold_pat_type<F>(f: &mut F, node: crate::PatType) -> crate::PatType
+where
+    F: Fold + ?Sized,
+{
+    crate::PatType {
+        attrs: fold_vec(node.attrs, f, F::fold_attribute),
+        pat: Box::new(f.fold_pat(*node.pat)),
+        colon_token: node.colon_token,
+        ty: Box::new(f.fold_type(*node.ty)),
+    }
+}
+
+pub fn fold_pat_wild<F>(f: &mut F, node: crate::PatWild) -> crate::PatWild
+where
+    F: Fold + ?Sized,
+{
+    crate::PatWild {
+        attrs: fold_vec(node.attrs, f, F::fold_attribute),
+        underscore_token: node.underscore_token,
+    }
+}
+
+pub fn fold_path<F>(f: &mut F, node: syn::Path) -> syn::Path
+where
+    F: Fold + ?Sized,
+{
+    syn::fold::fold_path(&mut Syn(f), node)
+}
+
+pub fn fold_receiver<F>(f: &mut F, node: crate::Receiver) -> crate::Receiver
+where
+    F: Fold + ?Sized,
+{
+    crate::Receiver {
+        attrs: fold_vec(node.attrs, f, F::fold_attribute),
+        reference: node
+            .reference
+            .map(|(and_token, lifetime)| (and_token, lifetime.map(|it| f.fold_lifetime(it)))),
+        mutability: node.mutability,
+        self_token: node.self_token,
+        colon_token: node.colon_token,
+        ty: Box::new(f.fold_type(*node.ty)),
+    }
+}
+
+pub fn fold_return_type<F>(f: &mut F, node: syn::ReturnType) -> syn::ReturnType
+where
+    F: Fold + ?Sized,
+{
+    syn::fold::fold_return_type(&mut Syn(f), node)
+}
+
+pub fn fold_signature<F>(f: &mut F, node: crate::Signature) -> crate::Signature
+where
+    F: Fold + ?Sized,
+{
+    crate::Signature {
+        constness: node.constness,
+        asyncness: node.asyncness,
+        unsafety: node.unsafety,
+        abi: node.abi.map(|it| f.fold_abi(it)),
+        fn_token: node.fn_token,
+        ident: f.fold_ident(node.ident),
+        generics: f.fold_generics(node.generics),
+        paren_token: node.paren_token,
+        inputs: fold_punctuated(node.inputs, f, F::fold_fn_arg),
+        variadic: node.variadic.map(|it| f.fold_variadic(it)),
+        output: f.fold_return_type(node.output),
+    }
+}
+
+pub fn fold_stmt<F>(f: &mut F, node: crate::Stmt) -> crate::Stmt
+where
+    F: Fold + ?Sized,
+{
+    match node {
+        crate::Stmt::Local(it) => crate::Stmt::Local(f.fold_local(it)),
+        crate::Stmt::Macro(it) => crate::Stmt::Macro(f.fold_stmt_macro(it)),
+        // Not parsed.
+        crate::Stmt::Item(..) | crate::Stmt::Expr(..) => node,
+    }
+}
+
+pub fn fold_stmt_macro<F>(f: &mut F, node: crate::StmtMacro) -> crate::StmtMacro
+where
+    F: Fold + ?Sized,
+{
+    crate::StmtMacro {
+        attrs: fold_vec(node.attrs, f, F::fold_attribute),
+        mac: f.fold_macro(node.mac),
+        semi_token: node.semi_token,
+    }
+}
+
+pub fn fold_type<F>(f: &mut F, node: syn::Type) -> syn::Type
+where
+    F: Fold + ?Sized,
+{
+    syn::fold::fold_type(&mut Syn(f), node)
+}
+
+pub fn fold_variadic<F>(f: &mut F, node: crate::Variadic) -> crate::Variadic
+where
+    F: Fold + ?Sized,
+{
+    crate::Variadic {
+        attrs: fold_vec(node.attrs, f, F::fold_attribute),
+        pat: node.pat.map(|(pat, colon_token)| (Box::new(f.fold_pat(*pat)), colon_token)),
+        dots: node.dots,
+        comma: node.comma,
+    }
+}
+
+pub fn fold_visibility<F>(f: &mut F, node: syn::Visibility) -> syn::Visibility
+where
+    F: Fold + ?Sized,
+{
+    syn::fold::fold_visibility(&mut Syn(f), node)
+}
+
+/// Passes the nodes of the types of syn back to a [`Fold`].
+struct Syn<'a, V: ?Sized>(&'a mut V);
+impl<F> syn::fold::Fold for Syn<'_, F>
+where
+    F: Fold + ?Sized,
+{
+    fn fold_abi(&mut self, i: syn::Abi) -> syn::Abi {
+        self.0.fold_abi(i)
+    }
+    fn fold_attribute(&mut self, i: syn::Attribute) -> syn::Attribute {
+        self.0.fold_attribute(i)
+    }
+    fn fold_expr_lit(&mut self, i: syn::ExprLit) -> syn::ExprLit {
+        self.0.fold_expr_lit(i)
+    }
+    fn fold_expr_path(&mut self, i: syn::ExprPath) -> syn::ExprPath {
+        self.0.fold_expr_path(i)
+    }
+    fn fold_generics(&mut self, i: syn::Generics) -> syn::Generics {
+        self.0.fold_generics(i)
+    }
+    fn fold_ident(&mut self, i: syn::Ident) -> syn::Ident {
+        self.0.fold_ident(i)
+    }
+    fn fold_lifetime(&mut self, i: syn::Lifetime) -> syn::Lifetime {
+        self.0.fold_lifetime(i)
+    }
+    fn fold_macro(&mut self, i: syn::Macro) -> syn::Macro {
+        self.0.fold_macro(i)
+    }
+    fn fold_member(&mut self, i: syn::Member) -> syn::Member {
+        self.0.fold_member(i)
+    }
+    fn fold_path(&mut self, i: syn::Path) -> syn::Path {
+        self.0.fold_path(i)
+    }
+    fn fold_return_type(&mut self, i: syn::ReturnType) -> syn::ReturnType {
+        self.0.fold_return_type(i)
+    }
+    fn fold_type(&mut self, i: syn::Type) -> syn::Type {
+        self.0.fold_type(i)
+    }
+    fn fold_visibility(&mut self, i: syn::Visibility) -> syn::Visibility {
+        self.0.fold_visibility(i)
+    }
+}
+
+fn fold_vec<T, F, G>(vec: Vec<T>, f: &mut F, mut op: G) -> Vec<T>
+where
+    F: ?Sized,
+    G: FnMut(&mut F, T) -> T,
+{
+    vec.into_iter().map(|it| op(f, it)).collect()
+}
+
+fn fold_punctuated<T, P, F, G>(
+    punctuated: Punctuated<T, P>,
+    f: &mut F,
+    mut op: G,
+) -> Punctuated<T, P>
+where
+    F: ?Sized,
+    G: FnMut(&mut F, T) -> T,
+{
+    punctuated
+        .into_pairs()
+        .map(|pair| match pair {
+            Pair::Punctuated(it, punct) => Pair::Punctuated(op(f, it), punct),
+            Pair::End(it) => Pair::End(op(f, it)),
+        })
+        .collect()
+}
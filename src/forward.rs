@@ -0,0 +1,138 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Forwarding the arguments of a function to another call.
+//
+// Wrappers generated by attribute macros usually need to call the original
+// function with the same arguments, which is only possible if every argument
+// is bound to a name. `Signature::normalize_args` gives every argument a fresh
+// name and moves its pattern into a `let` statement, and
+// `Signature::forward_call` builds the call from the normalized signature.
+
+use alloc::vec::Vec;
+use core::mem;
+
+use proc_macro2::{Span, TokenStream};
+use quote::{ToTokens, format_ident, quote};
+use syn::{Error, GenericParam, Result, Token};
+
+use crate::{Local, LocalInit, Pat, PatIdent, Signature};
+
+impl Signature {
+    /// Replaces the pattern of each argument other than `self` with a fresh
+    /// identifier, and returns `let` statements that bind the original
+    /// patterns to them.
+    ///
+    /// The statements should be inserted at the start of the function body, so
+    /// that the body sees the same bindings as before.
+    ///
+    /// The fresh identifiers are `__arg0`, `__arg1`, ... with
+    /// [`Span::mixed_site`], so they cannot collide with identifiers written by
+    /// the user.
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(not(all(feature = "parsing", feature = "printing")), doc = "```ignore")]
+    #[cfg_attr(all(feature = "parsing", feature = "printing"), doc = "```")]
+    /// use quote::quote;
+    /// use syn_mid::ItemFn;
+    ///
+    /// let mut item: ItemFn = syn::parse_quote! {
+    ///     async fn f<T>(&self, (a, b): (T, T), mut c: u8) {}
+    /// };
+    /// let lets = item.sig.normalize_args();
+    /// let call = item.sig.forward_call(quote!(Self::__f)).unwrap();
+    /// assert_eq!(
+    ///     quote!(#(#lets)*).to_string(),
+    ///     quote!(let (a, b) = __arg0; let mut c = __arg1;).to_string(),
+    /// );
+    /// assert_eq!(
+    ///     call.to_string(),
+    ///     quote!(Self::__f::<T>(self, __arg0, __arg1).await).to_string(),
+    /// );
+    /// ```
+    pub fn normalize_args(&mut self) -> Vec<Local> {
+        let mut lets = Vec::new();
+        for (i, arg) in self.typed_inputs_mut().enumerate() {
+            let span = Span::mixed_site();
+            let ident = format_ident!("__arg{}", i, span = span);
+            let pat = mem::replace(
+                &mut *arg.pat,
+                Pat::Ident(PatIdent {
+                    attrs: Vec::new(),
+                    by_ref: None,
+                    mutability: None,
+                    ident: ident.clone(),
+                }),
+            );
+            lets.push(Local {
+                attrs: Vec::new(),
+                let_token: Token![let](span),
+                pat,
+                init: Some(LocalInit {
+                    eq_token: Token![=](span),
+                    expr: ident.into_token_stream(),
+                    diverge: None,
+                }),
+                semi_token: Token![;](span),
+            });
+        }
+        lets
+    }
+
+    /// Returns an expression that calls `callee` with the arguments of this
+    /// signature.
+    ///
+    /// The receiver, if any, is passed as the first argument, so `callee`
+    /// should be a path such as `Self::name`. The type and const parameters
+    /// of this signature are passed by turbofish, and the call is followed by
+    /// `.await` if this signature is `async`.
+    ///
+    /// Every argument other than `self` must be a plain identifier without
+    /// `ref`; call [`normalize_args`](Self::normalize_args) first if the
+    /// signature may contain other patterns. Functions with a variadic
+    /// argument cannot be forwarded.
+    pub fn forward_call<T>(&self, callee: T) -> Result<TokenStream>
+    where
+        T: ToTokens,
+    {
+        if let Some(variadic) = &self.variadic {
+            return Err(Error::new_spanned(
+                variadic,
+                "cannot forward the arguments of a variadic function",
+            ));
+        }
+
+        let mut args = Vec::new();
+        if let Some(receiver) = self.receiver() {
+            args.push(receiver.self_token.to_token_stream());
+        }
+        for arg in self.typed_inputs() {
+            match &*arg.pat {
+                Pat::Ident(pat) if pat.by_ref.is_none() => {
+                    args.push(pat.ident.to_token_stream());
+                }
+                pat => {
+                    return Err(Error::new_spanned(
+                        pat,
+                        "cannot forward an argument that is not bound to an identifier",
+                    ));
+                }
+            }
+        }
+
+        let params: Vec<_> = self
+            .generics
+            .params
+            .iter()
+            .filter_map(|param| match param {
+                GenericParam::Type(param) => Some(&param.ident),
+                GenericParam::Const(param) => Some(&param.ident),
+                GenericParam::Lifetime(_) => None,
+            })
+            .collect();
+        let turbofish = if params.is_empty() { None } else { Some(quote!(::<#(#params),*>)) };
+        let await_ = self.asyncness.as_ref().map(|_| quote!(.await));
+
+        Ok(quote!(#callee #turbofish (#(#args),*) #await_))
+    }
+}
@@ -2,15 +2,21 @@
 
 // Based on https://github.com/dtolnay/syn/blob/2.0.37/src/item.rs.
 
+#[cfg(feature = "clone-impls")]
+use alloc::borrow::Cow;
 use alloc::{boxed::Box, vec::Vec};
+#[cfg(feature = "extra-traits")]
+use core::hash::{Hash, Hasher};
 
 use proc_macro2::TokenStream;
 use syn::{
-    Abi, Attribute, Generics, Ident, Lifetime, ReturnType, Token, Type, Visibility,
+    Abi, Attribute, Generics, Ident, Lifetime, ReturnType, Token, Type, TypeTuple, Visibility,
     punctuated::Punctuated, token,
 };
 
 use super::{Pat, PatType};
+#[cfg(feature = "extra-traits")]
+use crate::tt::TokenStreamHelper;
 
 ast_struct! {
     /// A free-standing function: `fn process(n: usize) -> Result<()> { ...
@@ -25,13 +31,34 @@ ast_struct! {
 
 ast_struct! {
     /// A braced block containing Rust statements.
-    pub struct Block {
+    pub struct Block #manual_extra_traits {
         pub brace_token: token::Brace,
+        /// Inner attributes at the start of the block: `#![allow(...)]`.
+        pub inner_attrs: Vec<Attribute>,
         /// Statements in a block
         pub stmts: TokenStream,
     }
 }
 
+#[cfg(feature = "extra-traits")]
+impl Eq for Block {}
+
+#[cfg(feature = "extra-traits")]
+impl PartialEq for Block {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner_attrs == other.inner_attrs
+            && TokenStreamHelper(&self.stmts) == TokenStreamHelper(&other.stmts)
+    }
+}
+
+#[cfg(feature = "extra-traits")]
+impl Hash for Block {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner_attrs.hash(state);
+        TokenStreamHelper(&self.stmts).hash(state);
+    }
+}
+
 ast_struct! {
     /// A function signature in a trait or implementation: `unsafe fn
     /// initialize(&self)`.
@@ -85,23 +112,212 @@ ast_struct! {
     }
 }
 
+impl Signature {
+    /// Returns the `self` argument of this signature, if any.
+    #[must_use]
+    pub fn receiver(&self) -> Option<&Receiver> {
+        self.inputs.first()?.receiver()
+    }
+
+    /// Returns a mutable reference to the `self` argument of this signature,
+    /// if any.
+    pub fn receiver_mut(&mut self) -> Option<&mut Receiver> {
+        self.inputs.first_mut()?.receiver_mut()
+    }
+
+    /// Returns an iterator over the arguments of this signature other than
+    /// `self`.
+    pub fn typed_inputs(&self) -> impl Iterator<Item = &PatType> {
+        self.inputs.iter().filter_map(FnArg::typed)
+    }
+
+    /// Returns an iterator over mutable references to the arguments of this
+    /// signature other than `self`.
+    pub fn typed_inputs_mut(&mut self) -> impl Iterator<Item = &mut PatType> {
+        self.inputs.iter_mut().filter_map(FnArg::typed_mut)
+    }
+
+    /// Returns the return type of this signature.
+    ///
+    /// If the return type is omitted, this returns `()`. This requires the
+    /// `clone-impls` feature, since [`Cow`] requires [`Type`] to be [`Clone`].
+    #[cfg(feature = "clone-impls")]
+    #[must_use]
+    pub fn output_type(&self) -> Cow<'_, Type> {
+        match &self.output {
+            ReturnType::Default => Cow::Owned(self.unit_type()),
+            ReturnType::Type(_, ty) => Cow::Borrowed(ty),
+        }
+    }
+
+    /// Returns a mutable reference to the return type of this signature.
+    ///
+    /// **Note:** If the return type is omitted, this modifies the signature
+    /// by inserting an explicit `-> ()`, even if the returned reference is
+    /// never written to. Use [`output_type`](Self::output_type) to read the
+    /// return type without changing the signature.
+    pub fn output_type_mut(&mut self) -> &mut Type {
+        if let ReturnType::Default = self.output {
+            let span = self.paren_token.span.close();
+            self.output = ReturnType::Type(Token![->](span), Box::new(self.unit_type()));
+        }
+        match &mut self.output {
+            ReturnType::Type(_, ty) => ty,
+            ReturnType::Default => unreachable!(),
+        }
+    }
+
+    /// Returns `true` if this signature has a `self` argument.
+    #[must_use]
+    pub fn is_method(&self) -> bool {
+        self.receiver().is_some()
+    }
+
+    /// Returns `true` if this signature has a variadic argument: `...`.
+    #[must_use]
+    pub fn has_variadic(&self) -> bool {
+        self.variadic.is_some()
+    }
+
+    fn unit_type(&self) -> Type {
+        Type::Tuple(TypeTuple {
+            paren_token: token::Paren(self.paren_token.span.close()),
+            elems: Punctuated::new(),
+        })
+    }
+}
+
+impl FnArg {
+    /// Returns the `self` argument if this is a [`FnArg::Receiver`].
+    #[must_use]
+    pub fn receiver(&self) -> Option<&Receiver> {
+        match self {
+            FnArg::Receiver(receiver) => Some(receiver),
+            FnArg::Typed(_) => None,
+        }
+    }
+
+    /// Returns a mutable reference to the `self` argument if this is a
+    /// [`FnArg::Receiver`].
+    pub fn receiver_mut(&mut self) -> Option<&mut Receiver> {
+        match self {
+            FnArg::Receiver(receiver) => Some(receiver),
+            FnArg::Typed(_) => None,
+        }
+    }
+
+    /// Returns the pattern and type if this is a [`FnArg::Typed`].
+    #[must_use]
+    pub fn typed(&self) -> Option<&PatType> {
+        match self {
+            FnArg::Receiver(_) => None,
+            FnArg::Typed(arg) => Some(arg),
+        }
+    }
+
+    /// Returns a mutable reference to the pattern and type if this is a
+    /// [`FnArg::Typed`].
+    pub fn typed_mut(&mut self) -> Option<&mut PatType> {
+        match self {
+            FnArg::Receiver(_) => None,
+            FnArg::Typed(arg) => Some(arg),
+        }
+    }
+}
+
+#[cfg(feature = "parsing")]
 mod parsing {
     use alloc::{boxed::Box, vec, vec::Vec};
 
+    use proc_macro2::TokenStream;
+    #[cfg(feature = "printing")]
+    use quote::ToTokens as _;
     use syn::{
-        Abi, Attribute, Error, Generics, Ident, Lifetime, Path, ReturnType, Token, Type, TypePath,
-        TypeReference, Visibility, braced, parenthesized,
-        parse::{Parse, ParseStream, Result, discouraged::Speculative as _},
+        Abi, Attribute, Error, Generics, Ident, Lifetime, LitStr, Path, ReturnType, Token, Type,
+        TypePath, TypeReference, Visibility, braced, parenthesized,
+        parse::{Parse, ParseStream, Parser as _, Result, discouraged::Speculative as _},
         punctuated::Punctuated,
     };
 
     use super::{Block, FnArg, ItemFn, Receiver, Signature, Variadic};
-    use crate::pat::{Pat, PatType, PatWild};
+    use crate::{
+        pat::{Pat, PatType, PatWild},
+        stmt::Stmt,
+    };
+
+    impl Block {
+        /// Returns the `fn` items among the top-level statements of this
+        /// block.
+        ///
+        /// `fn` items nested in other statements, for example in the body of
+        /// an `if` or in an `impl` block, are not included.
+        pub fn nested_fns(&self) -> Result<Vec<ItemFn>> {
+            let mut fns = Vec::new();
+            for stmt in self.split_stmts()? {
+                if let Stmt::Item(item) = stmt {
+                    if let Some(item) = item_fn.parse2(item)? {
+                        fns.push(item);
+                    }
+                }
+            }
+            Ok(fns)
+        }
+
+        /// Calls `f` on each `fn` item among the top-level statements of this
+        /// block, and writes the modified items back to
+        /// [`stmts`](Self::stmts).
+        ///
+        /// The tokens of the other statements, including the spacing of
+        /// their punctuation, are left unchanged.
+        #[cfg(feature = "printing")]
+        pub fn update_nested_fns<F>(&mut self, mut f: F) -> Result<()>
+        where
+            F: FnMut(&mut ItemFn),
+        {
+            let mut stmts = TokenStream::new();
+            for (stmt, tokens) in Self::parse_within_verbatim.parse2(self.stmts.clone())? {
+                if let Stmt::Item(item) = stmt {
+                    if let Some(mut item) = item_fn.parse2(item)? {
+                        f(&mut item);
+                        item.to_tokens(&mut stmts);
+                        continue;
+                    }
+                }
+                stmts.extend(tokens);
+            }
+            self.stmts = stmts;
+            Ok(())
+        }
+    }
+
+    /// Parses the tokens of an item statement as `ItemFn` if it is a function.
+    fn item_fn(input: ParseStream<'_>) -> Result<Option<ItemFn>> {
+        let ahead = input.fork();
+        ahead.call(Attribute::parse_outer)?;
+        let _: Visibility = ahead.parse()?;
+        let _: Option<Token![const]> = ahead.parse()?;
+        let _: Option<Token![async]> = ahead.parse()?;
+        let _: Option<Token![unsafe]> = ahead.parse()?;
+        if ahead.peek(Token![extern]) {
+            let _: Token![extern] = ahead.parse()?;
+            let _: Option<LitStr> = ahead.parse()?;
+        }
+        if ahead.peek(Token![fn]) {
+            input.parse().map(Some)
+        } else {
+            input.parse::<TokenStream>()?;
+            Ok(None)
+        }
+    }
 
     impl Parse for Block {
         fn parse(input: ParseStream<'_>) -> Result<Self> {
             let content;
-            Ok(Self { brace_token: braced!(content in input), stmts: content.parse()? })
+            Ok(Self {
+                brace_token: braced!(content in input),
+                inner_attrs: content.call(Attribute::parse_inner)?,
+                stmts: content.parse()?,
+            })
         }
     }
 
@@ -314,6 +530,7 @@ mod parsing {
     }
 }
 
+#[cfg(feature = "printing")]
 mod printing {
     use proc_macro2::TokenStream;
     use quote::{ToTokens, TokenStreamExt as _};
@@ -333,6 +550,7 @@ mod printing {
     impl ToTokens for Block {
         fn to_tokens(&self, tokens: &mut TokenStream) {
             self.brace_token.surround(tokens, |tokens| {
+                tokens.append_all(&self.inner_attrs);
                 tokens.append_all(self.stmts.clone());
             });
         }
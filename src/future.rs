@@ -0,0 +1,177 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Desugaring of `async fn` into a function returning `impl Future`.
+
+use alloc::vec::Vec;
+use core::mem;
+
+use quote::{ToTokens, quote};
+use syn::{
+    GenericParam, Lifetime, LifetimeParam, ReturnType, TypeImplTrait, TypeParamBound, parse_quote,
+};
+
+use crate::{
+    ItemFn, Signature, lifetime,
+    walk::{VisitTypeMut, walk_type_impl_trait},
+};
+
+impl Signature {
+    /// Turns `async fn` into `fn` returning `impl Future`.
+    ///
+    /// `async fn f(&self, x: &T) -> R` becomes:
+    ///
+    /// ```text
+    /// fn f<'__fut, '__life0, '__life1>(&'__life0 self, x: &'__life1 T)
+    ///     -> impl Future<Output = R> + Captures<'__life0> + Captures<'__life1> + '__fut
+    /// where
+    ///     '__life0: '__fut,
+    ///     '__life1: '__fut,
+    ///     Self: '__fut,
+    /// ```
+    ///
+    /// Elided lifetimes in the arguments are given names, and every lifetime
+    /// and type parameter of the signature, as well as `Self` if it is a
+    /// method, is required to outlive the returned future. Argument-position
+    /// `impl Trait` gets the same bound. If `send` is `true`, the returned
+    /// future is also bounded by `Send`.
+    ///
+    /// `captures` is the path to a trait that every type implements, and that
+    /// is used to make the returned `impl Future` capture every lifetime of
+    /// the signature, as an `async fn` does. Without it, a lifetime that only
+    /// appears in an invariant position, like `'b` in `&'a mut Vec<&'b u8>`,
+    /// is rejected by the compiler. The trait must be defined in the crate
+    /// that the tokens are emitted to:
+    ///
+    /// ```
+    /// #[doc(hidden)]
+    /// pub trait Captures<'a> {}
+    /// impl<T: ?Sized> Captures<'_> for T {}
+    /// ```
+    ///
+    /// Lifetime parameters of an enclosing `impl` block do not appear in the
+    /// signature and are not captured, so `Self` must not be invariant over
+    /// them.
+    ///
+    /// This only changes the signature; see [`ItemFn::desugar_async`] to also
+    /// wrap the function body in an `async move` block. Does nothing if the
+    /// signature is not `async`.
+    pub fn desugar_async<T>(&mut self, send: bool, captures: T)
+    where
+        T: ToTokens,
+    {
+        let Some(async_token) = self.asyncness.take() else { return };
+        let fut = Lifetime::new("'__fut", async_token.span);
+
+        let elided = lifetime::name_elided_inputs(self);
+        let mut v = BoundImplTrait { lifetime: &fut };
+        for arg in self.typed_inputs_mut() {
+            v.visit_type_mut(&mut arg.ty);
+        }
+
+        let mut bounded = Vec::new();
+        let mut lifetimes = Vec::new();
+        for param in &self.generics.params {
+            match param {
+                GenericParam::Lifetime(param) => {
+                    bounded.push(param.lifetime.to_token_stream());
+                    lifetimes.push(param.lifetime.clone());
+                }
+                GenericParam::Type(param) => bounded.push(param.ident.to_token_stream()),
+                GenericParam::Const(_) => {}
+            }
+        }
+        bounded.extend(elided.iter().map(Lifetime::to_token_stream));
+        lifetimes.extend(elided.iter().cloned());
+        if self.is_method() {
+            bounded.push(quote!(Self));
+        }
+
+        self.generics.params.insert(0, GenericParam::Lifetime(LifetimeParam::new(fut.clone())));
+        for (i, lifetime) in elided.into_iter().enumerate() {
+            self.generics
+                .params
+                .insert(i + 1, GenericParam::Lifetime(LifetimeParam::new(lifetime)));
+        }
+        let where_clause = self.generics.make_where_clause();
+        for param in bounded {
+            where_clause.predicates.push(parse_quote!(#param: #fut));
+        }
+
+        let output = match mem::replace(&mut self.output, ReturnType::Default) {
+            ReturnType::Default => quote!(()),
+            ReturnType::Type(_, ty) => ty.into_token_stream(),
+        };
+        let send = if send { Some(quote!(+ ::core::marker::Send)) } else { None };
+        let captures = lifetimes.iter().map(|lifetime| quote!(+ #captures<#lifetime>));
+        self.output = parse_quote! {
+            -> impl ::core::future::Future<Output = #output> #send #(#captures)* + #fut
+        };
+    }
+}
+
+impl ItemFn {
+    /// Turns `async fn` into `fn` returning `impl Future`, and wraps the
+    /// function body in an `async move` block.
+    ///
+    /// See [`Signature::desugar_async`] for the changes to the signature and
+    /// the `captures` trait.
+    /// The arguments are normalized by [`Signature::normalize_args`] and
+    /// rebound at the start of the `async move` block, so that all of them
+    /// are moved into the returned future as in an `async fn`. Does nothing
+    /// if the function is not `async`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quote::quote;
+    /// use syn_mid::ItemFn;
+    ///
+    /// let mut item: ItemFn = syn::parse_quote! {
+    ///     async fn f(x: &u8) -> u8 { *x }
+    /// };
+    /// item.desugar_async(true, quote!(::my_crate::__private::Captures));
+    /// assert_eq!(
+    ///     quote!(#item).to_string(),
+    ///     quote! {
+    ///         fn f<'__fut, '__life0>(__arg0: &'__life0 u8)
+    ///             -> impl ::core::future::Future<Output = u8>
+    ///                 + ::core::marker::Send
+    ///                 + ::my_crate::__private::Captures<'__life0>
+    ///                 + '__fut
+    ///         where
+    ///             '__life0: '__fut
+    ///         {
+    ///             async move {
+    ///                 let x = __arg0;
+    ///                 *x
+    ///             }
+    ///         }
+    ///     }
+    ///     .to_string(),
+    /// );
+    /// ```
+    pub fn desugar_async<T>(&mut self, send: bool, captures: T)
+    where
+        T: ToTokens,
+    {
+        if self.sig.asyncness.is_none() {
+            return;
+        }
+        let lets = self.sig.normalize_args();
+        self.sig.desugar_async(send, captures);
+        let stmts = mem::take(&mut self.block.stmts);
+        self.block.stmts = quote!(async move { #(#lets)* #stmts });
+    }
+}
+
+/// Adds a lifetime bound to each `impl Trait` type.
+struct BoundImplTrait<'a> {
+    lifetime: &'a Lifetime,
+}
+
+impl VisitTypeMut for BoundImplTrait<'_> {
+    fn visit_type_impl_trait_mut(&mut self, ty: &mut TypeImplTrait) {
+        walk_type_impl_trait(self, ty);
+        ty.bounds.push(TypeParamBound::Lifetime(self.lifetime.clone()));
+    }
+}
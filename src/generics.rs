@@ -0,0 +1,342 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Transformations of the generic parameters of function signatures.
+
+#[cfg(all(feature = "parsing", feature = "printing"))]
+use alloc::boxed::Box;
+use alloc::{format, vec::Vec};
+use core::mem;
+
+use proc_macro2::Span;
+#[cfg(all(feature = "parsing", feature = "printing"))]
+use quote::ToTokens as _;
+use syn::{GenericParam, Ident, Path, Token, Type, TypeParam, TypePath};
+#[cfg(all(feature = "parsing", feature = "printing"))]
+use syn::{PredicateType, QSelf, TypeParen, WherePredicate, token};
+
+#[cfg(all(feature = "parsing", feature = "printing"))]
+use crate::walk::{walk_return_type, walk_type_path};
+use crate::{
+    Signature,
+    walk::{VisitTypeMut, walk_type, walk_type_impl_trait},
+};
+
+/// A generic parameter introduced in place of an argument-position
+/// `impl Trait`.
+///
+/// See [`Signature::lift_impl_trait_args`].
+#[cfg_attr(feature = "extra-traits", derive(Debug, Eq, PartialEq, Hash))]
+#[derive(Clone)]
+pub struct ImplTraitParam {
+    /// The name of the new type parameter.
+    pub ident: Ident,
+    /// The index in [`Signature::inputs`] of the argument whose type
+    /// contained the `impl Trait`.
+    pub input: usize,
+}
+
+impl Signature {
+    /// Replaces each argument-position `impl Trait` with a new type
+    /// parameter, and returns the new parameters in the order they were added
+    /// to the generics.
+    ///
+    /// The new parameters are named `T0`, `T1`, ..., skipping names already
+    /// used by the generics of this signature, and have the bounds of the
+    /// `impl Trait` they replace. Nested `impl Trait`, as in
+    /// `impl Iterator<Item = impl Display>`, are also replaced, and the outer
+    /// one comes first.
+    ///
+    /// Callers that pass generic arguments explicitly can use the returned
+    /// parameters to place the types of the arguments in the turbofish.
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(not(all(feature = "parsing", feature = "printing")), doc = "```ignore")]
+    #[cfg_attr(all(feature = "parsing", feature = "printing"), doc = "```")]
+    /// use quote::quote;
+    /// use syn_mid::ItemFn;
+    ///
+    /// let mut item: ItemFn = syn::parse_quote! {
+    ///     fn f(x: impl Into<String>, y: Vec<impl Copy>) {}
+    /// };
+    /// let params = item.sig.lift_impl_trait_args();
+    /// assert_eq!(params[0].ident, "T0");
+    /// assert_eq!(params[1].input, 1);
+    /// let sig = &item.sig;
+    /// assert_eq!(
+    ///     quote!(#sig).to_string(),
+    ///     quote!(fn f<T0: Into<String>, T1: Copy>(x: T0, y: Vec<T1>)).to_string(),
+    /// );
+    /// ```
+    pub fn lift_impl_trait_args(&mut self) -> Vec<ImplTraitParam> {
+        let mut v = LiftImplTrait {
+            taken: self
+                .generics
+                .params
+                .iter()
+                .filter_map(|param| match param {
+                    GenericParam::Type(param) => Some(param.ident.clone()),
+                    GenericParam::Const(param) => Some(param.ident.clone()),
+                    GenericParam::Lifetime(_) => None,
+                })
+                .collect(),
+            next: 0,
+            input: 0,
+            params: Vec::new(),
+        };
+        for (i, arg) in self.inputs.iter_mut().enumerate() {
+            if let Some(arg) = arg.typed_mut() {
+                v.input = i;
+                v.visit_type_mut(&mut arg.ty);
+            }
+        }
+
+        let mut lifted = Vec::with_capacity(v.params.len());
+        for (param, input) in v.params {
+            lifted.push(ImplTraitParam { ident: param.ident.clone(), input });
+            self.generics.params.push(GenericParam::Type(param));
+        }
+        lifted
+    }
+}
+
+#[cfg(all(feature = "parsing", feature = "printing"))]
+impl Signature {
+    /// Replaces `Self` and generic type parameters with the given types
+    /// throughout this signature.
+    ///
+    /// Each element of `substs` is the name of a type parameter, or `Self`,
+    /// and the type to replace it with. (`Self` is a keyword and cannot be
+    /// parsed as an `Ident`; create it with `Ident::new` or `format_ident!`.)
+    /// The replaced parameters are removed from the generics, and their inline
+    /// bounds are moved to the `where` clause: `fn f<T: Copy>(x: T)` with `T`
+    /// replaced by `u8` becomes `fn f(x: u8) where u8: Copy`. If `Self` is
+    /// replaced, a `self` argument is written with its type: `&self` becomes
+    /// `self: &Type`.
+    ///
+    /// Only paths that start with one of the names are replaced, so the `T`
+    /// in `module::T` or in `Iterator<T = u8>` is left alone. A path to an
+    /// associated item, `T::Item`, becomes `<Type>::Item`; if the associated
+    /// item belongs to a trait, the resulting path is only accepted by the
+    /// compiler if the trait can be inferred.
+    ///
+    /// See [`Block::substitute`](crate::Block::substitute) to also replace
+    /// them in the function body.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quote::{format_ident, quote};
+    /// use syn_mid::ItemFn;
+    ///
+    /// let mut item: ItemFn = syn::parse_quote! {
+    ///     fn eq<T: PartialEq>(&self, other: &T) -> Option<Self> {}
+    /// };
+    /// item.sig.substitute(&[
+    ///     (format_ident!("Self"), syn::parse_quote!(Foo)),
+    ///     (format_ident!("T"), syn::parse_quote!(Vec<u8>)),
+    /// ]);
+    /// let sig = &item.sig;
+    /// assert_eq!(
+    ///     quote!(#sig).to_string(),
+    ///     quote! {
+    ///         fn eq(self: &Foo, other: &Vec<u8>) -> Option<Foo> where Vec<u8>: PartialEq
+    ///     }
+    ///     .to_string(),
+    /// );
+    /// ```
+    pub fn substitute(&mut self, substs: &[(Ident, Type)]) {
+        let mut v = Substitute { substs };
+
+        let mut moved_bounds = Vec::new();
+        let params = mem::take(&mut self.generics.params);
+        for param in params.into_pairs() {
+            let (mut param, punct) = param.into_tuple();
+            if let GenericParam::Type(param) = &mut param {
+                if let Some((_, ty)) = substs.iter().find(|(ident, _)| *ident == param.ident) {
+                    if !param.bounds.is_empty() {
+                        let mut bounds = mem::take(&mut param.bounds);
+                        for bound in &mut bounds {
+                            v.visit_type_param_bound_mut(bound);
+                        }
+                        moved_bounds.push(WherePredicate::Type(PredicateType {
+                            lifetimes: None,
+                            bounded_ty: parenthesize(clone_type(ty), param.ident.span()),
+                            colon_token: Token![:](param.ident.span()),
+                            bounds,
+                        }));
+                    }
+                    continue;
+                }
+            }
+            self.generics.params.push_value(param);
+            if let Some(punct) = punct {
+                self.generics.params.push_punct(punct);
+            }
+        }
+        if self.generics.params.is_empty() {
+            self.generics.lt_token = None;
+            self.generics.gt_token = None;
+        }
+
+        for param in &mut self.generics.params {
+            match param {
+                GenericParam::Type(param) => {
+                    for bound in &mut param.bounds {
+                        v.visit_type_param_bound_mut(bound);
+                    }
+                    if let Some(default) = &mut param.default {
+                        v.visit_type_mut(default);
+                    }
+                }
+                GenericParam::Const(param) => v.visit_type_mut(&mut param.ty),
+                GenericParam::Lifetime(_) => {}
+            }
+        }
+        if let Some(where_clause) = &mut self.generics.where_clause {
+            for predicate in &mut where_clause.predicates {
+                if let WherePredicate::Type(predicate) = predicate {
+                    v.visit_type_mut(&mut predicate.bounded_ty);
+                    for bound in &mut predicate.bounds {
+                        v.visit_type_param_bound_mut(bound);
+                    }
+                }
+            }
+        }
+        if !moved_bounds.is_empty() {
+            self.generics.make_where_clause().predicates.extend(moved_bounds);
+        }
+
+        if let Some(receiver) = self.receiver_mut() {
+            v.visit_type_mut(&mut receiver.ty);
+            if receiver.colon_token.is_none() && substs.iter().any(|(ident, _)| ident == "Self") {
+                // `&mut self` -> `self: &mut Type`
+                if receiver.reference.take().is_some() {
+                    receiver.mutability = None;
+                }
+                receiver.colon_token = Some(Token![:](receiver.self_token.span));
+            }
+        }
+        for arg in self.typed_inputs_mut() {
+            v.visit_type_mut(&mut arg.ty);
+        }
+        walk_return_type(&mut v, &mut self.output);
+    }
+}
+
+/// Replaces paths that start with a substituted name.
+#[cfg(all(feature = "parsing", feature = "printing"))]
+struct Substitute<'a> {
+    substs: &'a [(Ident, Type)],
+}
+
+#[cfg(all(feature = "parsing", feature = "printing"))]
+impl Substitute<'_> {
+    fn find(&self, path: &Path) -> Option<&Type> {
+        let first = path.segments.first()?;
+        if path.leading_colon.is_some() || !first.arguments.is_none() {
+            return None;
+        }
+        self.substs.iter().find(|(ident, _)| *ident == first.ident).map(|(_, ty)| ty)
+    }
+}
+
+#[cfg(all(feature = "parsing", feature = "printing"))]
+impl VisitTypeMut for Substitute<'_> {
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        if let Type::Path(path) = ty {
+            if path.qself.is_none() && path.path.segments.len() == 1 {
+                if let Some(new) = self.find(&path.path) {
+                    let span = path.path.segments[0].ident.span();
+                    *ty = parenthesize(clone_type(new), span);
+                    return;
+                }
+            }
+        }
+        walk_type(self, ty);
+    }
+    fn visit_type_path_mut(&mut self, ty: &mut TypePath) {
+        walk_type_path(self, ty);
+        if ty.qself.is_some() {
+            return;
+        }
+        let Some(new) = self.find(&ty.path) else { return };
+        // `T::Item` -> `<Type>::Item`
+        let span = ty.path.segments[0].ident.span();
+        let new = clone_type(new);
+        let segments = mem::take(&mut ty.path.segments).into_pairs().skip(1).collect();
+        ty.qself = Some(QSelf {
+            lt_token: Token![<](span),
+            ty: Box::new(new),
+            position: 0,
+            as_token: None,
+            gt_token: Token![>](span),
+        });
+        ty.path = Path { leading_colon: Some(Token![::](span)), segments };
+    }
+}
+
+/// Clones a type by printing and parsing it, so that it does not require the
+/// "clone-impls" feature.
+#[cfg(all(feature = "parsing", feature = "printing"))]
+pub(crate) fn clone_type(ty: &Type) -> Type {
+    let tokens = ty.to_token_stream();
+    syn::parse2(tokens.clone()).unwrap_or(Type::Verbatim(tokens))
+}
+
+/// Wraps a trait object or `impl Trait` with multiple bounds in parentheses,
+/// so that it can be used where a `+` would be ambiguous: `&(dyn A + B)`.
+#[cfg(all(feature = "parsing", feature = "printing"))]
+pub(crate) fn parenthesize(ty: Type, span: Span) -> Type {
+    let multiple_bounds = match &ty {
+        Type::TraitObject(ty) => ty.bounds.len() > 1,
+        Type::ImplTrait(ty) => ty.bounds.len() > 1,
+        _ => false,
+    };
+    if multiple_bounds {
+        Type::Paren(TypeParen { paren_token: token::Paren(span), elem: Box::new(ty) })
+    } else {
+        ty
+    }
+}
+
+struct LiftImplTrait {
+    taken: Vec<Ident>,
+    next: usize,
+    input: usize,
+    params: Vec<(TypeParam, usize)>,
+}
+
+impl LiftImplTrait {
+    fn fresh(&mut self, span: Span) -> Ident {
+        loop {
+            let ident = Ident::new(&format!("T{}", self.next), span);
+            self.next += 1;
+            if !self.taken.contains(&ident) {
+                return ident;
+            }
+        }
+    }
+}
+
+impl VisitTypeMut for LiftImplTrait {
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        let Type::ImplTrait(impl_trait) = ty else {
+            walk_type(self, ty);
+            return;
+        };
+
+        let span = impl_trait.impl_token.span;
+        let ident = self.fresh(span);
+        let index = self.params.len();
+        self.params.push((TypeParam::from(ident.clone()), self.input));
+
+        let new = Type::Path(TypePath { qself: None, path: Path::from(ident) });
+        let Type::ImplTrait(mut impl_trait) = mem::replace(ty, new) else { unreachable!() };
+        walk_type_impl_trait(self, &mut impl_trait);
+
+        let param = &mut self.params[index].0;
+        param.colon_token = Some(Token![:](span));
+        param.bounds = mem::take(&mut impl_trait.bounds);
+    }
+}
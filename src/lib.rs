@@ -7,7 +7,7 @@
 
 Providing the features between "full" and "derive" of syn.
 
-This crate provides the following two unique data structures.
+This crate provides the following unique data structures.
 
 - [`syn_mid::ItemFn`] -- A function whose body is not parsed.
 
@@ -23,6 +23,13 @@ This crate provides the following two unique data structures.
   ^     ^
   ```
 
+- [`syn_mid::Stmt`] -- A statement whose expressions are not parsed.
+
+  ```text
+  let Some(x) = iter.next() else { return };
+  ^^^^^^^^^^^ ^ ...         ^^^^^^^^^^^^^^^
+  ```
+
 Other data structures are the same as data structures of [syn]. These are
 defined in this crate because they cannot be used in [syn] without "full"
 feature.
@@ -40,11 +47,29 @@ syn-mid = "0.6"
 
 ## Optional features
 
-- **`clone-impls`** — Clone impls for all syntax tree types.
+- **`parsing`** *(enabled by default)* — Ability to parse input tokens into a syntax tree node of a chosen type.
+- **`printing`** *(enabled by default)* — Ability to print a syntax tree node as tokens of Rust source code.
+
+  The transformations that print tokens, such as `Signature::forward_call`, require this feature. Those that also parse tokens, such as `Signature::substitute`, `Signature::desugar_async`, and the `Block::rewrite_*` methods, require both features.
+- **`clone-impls`** — Clone impls for all syntax tree types. `Signature::output_type` requires this feature, since it returns a `Cow<Type>`.
+- **`extra-traits`** — Debug, Eq, PartialEq, and Hash impls for all syntax tree types. The token streams that are not parsed, such as the statements of [`syn_mid::Block`], are compared and hashed by their string form. This enables the "extra-traits" feature of syn.
+- **`visit`** — Syntax tree traversal with the [`visit::Visit`] trait. This enables the "visit" feature of syn.
+- **`visit-mut`** — Syntax tree traversal to mutate the syntax tree in place with the [`visit_mut::VisitMut`] trait. This enables the "visit-mut" feature of syn.
+- **`fold`** — Syntax tree traversal to transform the nodes of an owned syntax tree with the [`fold::Fold`] trait. This enables the "fold" feature of syn.
+- **`arbitrary`** — [`Arbitrary`] impls for all syntax tree types, for fuzzing procedural macros. The generated syntax trees print to tokens that the parsers of this crate accept, and parsing them gives the same syntax trees. This enables the `printing` feature.
+- **`serde`** — Serialize and Deserialize impls for all syntax tree types. The data structures of this crate are serialized in the data format of [syn-serde], but the data structures of syn that they contain (types, generics, visibility, attributes, paths, literals, and so on) and the token streams that are not parsed, such as the statements of [`syn_mid::Block`], are serialized as strings of their token text, not structurally as in syn-serde. (Serializing them structurally would require the "full" feature of syn.) A syntax tree deserialized from JSON prints the same tokens as the original, except for spans, but tools that need the structure of those fields must parse the strings themselves, or convert the syntax tree to syn with the `convert` feature and use syn-serde. This enables the `parsing` and `printing` features.
+- **`convert`** — `From`/`TryFrom` conversions between the data structures of this crate and their counterparts in syn. This enables the "full" feature of syn.
+- **`syn-full`** — Enables the `full` submodule of the [`compat`] module, which re-exports the data structures of syn under the same names as `compat::mid`. This enables the "full" feature of syn.
 
 [syn]: https://github.com/dtolnay/syn
+[`Arbitrary`]: https://docs.rs/arbitrary/1/arbitrary/trait.Arbitrary.html
+[syn-serde]: https://github.com/taiki-e/syn-serde
 
 <!-- tidy:sync-markdown-to-rustdoc:end -->
+
+[`visit::Visit`]: https://docs.rs/syn-mid/latest/syn_mid/visit/trait.Visit.html
+[`visit_mut::VisitMut`]: https://docs.rs/syn-mid/latest/syn_mid/visit_mut/trait.VisitMut.html
+[`fold::Fold`]: https://docs.rs/syn-mid/latest/syn_mid/fold/trait.Fold.html
 */
 
 #![no_std]
@@ -81,17 +106,51 @@ extern crate std;
 #[macro_use]
 mod macros;
 
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+#[cfg(feature = "convert")]
+mod convert;
+mod expr;
+#[cfg(feature = "printing")]
+mod forward;
 mod func;
+#[cfg(all(feature = "parsing", feature = "printing"))]
+mod future;
+mod generics;
+mod lifetime;
 mod pat;
+#[cfg(feature = "parsing")]
 mod path;
+#[cfg(all(feature = "parsing", feature = "printing"))]
+mod rewrite;
+#[cfg(feature = "serde")]
+mod serde;
+mod stmt;
+#[cfg(feature = "extra-traits")]
+mod tt;
+#[cfg(feature = "parsing")]
+mod verbatim;
+mod walk;
+
+pub mod compat;
+#[cfg(feature = "fold")]
+pub mod fold;
+#[cfg(feature = "visit")]
+pub mod visit;
+#[cfg(feature = "visit-mut")]
+pub mod visit_mut;
 
 #[doc(no_inline)]
-pub use syn::ExprPath as PatPath;
+pub use syn::{ExprLit as PatLit, ExprPath as PatPath};
 
 pub use self::{
+    expr::{Arm, ExprMatch},
     func::{Block, FnArg, ItemFn, Receiver, Signature, Variadic},
+    generics::ImplTraitParam,
+    lifetime::ElidedLifetimes,
     pat::{
-        FieldPat, Pat, PatIdent, PatReference, PatRest, PatStruct, PatTuple, PatTupleStruct,
+        FieldPat, Pat, PatIdent, PatOr, PatReference, PatRest, PatStruct, PatTuple, PatTupleStruct,
         PatType, PatWild,
     },
+    stmt::{Local, LocalInit, Stmt, StmtMacro},
 };
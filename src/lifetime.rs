@@ -0,0 +1,275 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Lifetime elision in function signatures.
+
+use alloc::{format, vec::Vec};
+
+use proc_macro2::{Ident, Span};
+use syn::{
+    Error, GenericParam, Lifetime, LifetimeParam, ParenthesizedGenericArguments, Result, Type,
+    TypeBareFn, TypeReference,
+};
+
+use crate::{
+    Signature,
+    walk::{VisitTypeMut, walk_return_type, walk_type_reference},
+};
+
+/// The result of applying the lifetime elision rules to a [`Signature`].
+///
+/// See [`Signature::name_elided_lifetimes`].
+#[cfg_attr(feature = "extra-traits", derive(Debug, Eq, PartialEq, Hash))]
+#[derive(Clone)]
+pub struct ElidedLifetimes {
+    /// The names given to the elided lifetimes in the inputs, in order of
+    /// appearance.
+    pub inputs: Vec<Lifetime>,
+    /// The lifetime that elided lifetimes in the output refer to.
+    ///
+    /// This is the lifetime of `self` if `self` is taken by reference, or
+    /// otherwise the only lifetime in the inputs. `None` if neither rule
+    /// applies, in which case the output must not contain elided lifetimes.
+    pub output: Option<Lifetime>,
+}
+
+impl Signature {
+    /// Gives a name to each elided lifetime in the inputs of this signature,
+    /// and works out which lifetime elided lifetimes in the output refer to.
+    ///
+    /// Elided lifetimes are references without a lifetime (`&T`) and the
+    /// placeholder lifetime `'_`, including those in generic arguments
+    /// (`Iter<'_, T>`, `Vec<&T>`). They are replaced with fresh lifetimes
+    /// `'__life0`, `'__life1`, ..., skipping names that the generics of this
+    /// signature already declare. The new lifetimes are added to the generics
+    /// after the existing lifetime parameters. Function pointers and `Fn`
+    /// traits have their own elision scope and are left alone.
+    ///
+    /// Lifetimes hidden in paths, as in `fn f(s: Formatter) -> Display`
+    /// where `Formatter` has a lifetime parameter, cannot be seen without
+    /// type information and are not handled.
+    ///
+    /// The output is not changed, and still refers to the same lifetime; see
+    /// [`make_lifetimes_explicit`](Self::make_lifetimes_explicit) to also
+    /// replace elided lifetimes in the output.
+    pub fn name_elided_lifetimes(&mut self) -> ElidedLifetimes {
+        let inputs = name_elided_inputs(self);
+        let start = self.generics.lifetimes().count();
+        for (i, lifetime) in inputs.iter().enumerate() {
+            let param = GenericParam::Lifetime(LifetimeParam::new(lifetime.clone()));
+            self.generics.params.insert(start + i, param);
+        }
+        ElidedLifetimes { inputs, output: output_lifetime(self) }
+    }
+
+    /// Makes all lifetimes in this signature explicit.
+    ///
+    /// This is [`name_elided_lifetimes`](Self::name_elided_lifetimes)
+    /// followed by replacing the elided lifetimes in the output with
+    /// [`ElidedLifetimes::output`].
+    ///
+    /// Returns an error if the output contains elided lifetimes but the
+    /// elision rules do not determine what they refer to.
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(not(all(feature = "parsing", feature = "printing")), doc = "```ignore")]
+    #[cfg_attr(all(feature = "parsing", feature = "printing"), doc = "```")]
+    /// use quote::quote;
+    /// use syn_mid::ItemFn;
+    ///
+    /// let mut item: ItemFn = syn::parse_quote! {
+    ///     fn get(&self, key: &str) -> Option<&Value> {}
+    /// };
+    /// item.sig.make_lifetimes_explicit().unwrap();
+    /// let sig = &item.sig;
+    /// assert_eq!(
+    ///     quote!(#sig).to_string(),
+    ///     quote! {
+    ///         fn get<'__life0, '__life1>(&'__life0 self, key: &'__life1 str)
+    ///             -> Option<&'__life0 Value>
+    ///     }
+    ///     .to_string(),
+    /// );
+    /// ```
+    pub fn make_lifetimes_explicit(&mut self) -> Result<ElidedLifetimes> {
+        let elided = self.name_elided_lifetimes();
+        let mut v = ReplaceElided { lifetime: elided.output.as_ref(), error: None };
+        walk_return_type(&mut v, &mut self.output);
+        match v.error {
+            Some(e) => Err(e),
+            None => Ok(elided),
+        }
+    }
+}
+
+/// Gives a fresh name to each elided lifetime in the inputs of `sig`, and
+/// returns the new lifetimes in order of appearance.
+///
+/// The new lifetimes do not clash with the lifetime parameters of `sig`, but
+/// are not added to its generics.
+pub(crate) fn name_elided_inputs(sig: &mut Signature) -> Vec<Lifetime> {
+    let declared = sig.generics.lifetimes().map(|param| param.lifetime.ident.clone()).collect();
+    let mut v = NameElided { lifetimes: Vec::new(), declared, next: 0 };
+    if let Some(receiver) = sig.receiver_mut() {
+        if receiver.colon_token.is_none() {
+            if let Some((ampersand, lifetime)) = &mut receiver.reference {
+                if lifetime.as_ref().map_or(true, is_elided) {
+                    let new = v.fresh(ampersand.span);
+                    if let Type::Reference(ty) = &mut *receiver.ty {
+                        ty.lifetime = Some(new.clone());
+                    }
+                    *lifetime = Some(new);
+                }
+            }
+        } else {
+            v.visit_type_mut(&mut receiver.ty);
+        }
+    }
+    for arg in sig.typed_inputs_mut() {
+        v.visit_type_mut(&mut arg.ty);
+    }
+    v.lifetimes
+}
+
+/// Returns `true` if `lifetime` is the placeholder lifetime `'_`.
+pub(crate) fn is_elided(lifetime: &Lifetime) -> bool {
+    lifetime.ident == "_"
+}
+
+struct NameElided {
+    lifetimes: Vec<Lifetime>,
+    /// The names of the lifetime parameters that are already declared.
+    declared: Vec<Ident>,
+    next: usize,
+}
+
+impl NameElided {
+    fn fresh(&mut self, span: Span) -> Lifetime {
+        loop {
+            let lifetime = Lifetime::new(&format!("'__life{}", self.next), span);
+            self.next += 1;
+            if !self.declared.contains(&lifetime.ident) {
+                self.lifetimes.push(lifetime.clone());
+                return lifetime;
+            }
+        }
+    }
+}
+
+impl VisitTypeMut for NameElided {
+    fn visit_type_reference_mut(&mut self, ty: &mut TypeReference) {
+        if ty.lifetime.is_none() {
+            ty.lifetime = Some(self.fresh(ty.and_token.span));
+        }
+        walk_type_reference(self, ty);
+    }
+    fn visit_lifetime_mut(&mut self, lifetime: &mut Lifetime) {
+        if is_elided(lifetime) {
+            *lifetime = self.fresh(lifetime.apostrophe);
+        }
+    }
+    // Function pointers and `Fn` traits have their own elision scope.
+    fn visit_type_bare_fn_mut(&mut self, _ty: &mut TypeBareFn) {}
+    fn visit_parenthesized_generic_arguments_mut(
+        &mut self,
+        _args: &mut ParenthesizedGenericArguments,
+    ) {
+    }
+}
+
+/// Returns the lifetime that elided lifetimes in the output of `sig` refer to,
+/// assuming that there are no elided lifetimes in the inputs.
+///
+/// `sig` is only borrowed mutably because the type visitor requires it; it is
+/// not modified.
+fn output_lifetime(sig: &mut Signature) -> Option<Lifetime> {
+    let mut v = CollectLifetimes { lifetimes: Vec::new(), self_ref: None };
+    if let Some(receiver) = sig.receiver_mut() {
+        if let Some((_, lifetime)) = &receiver.reference {
+            return lifetime.clone();
+        }
+        v.visit_type_mut(&mut receiver.ty);
+        if v.self_ref.is_some() {
+            return v.self_ref;
+        }
+    }
+    for arg in sig.typed_inputs_mut() {
+        v.visit_type_mut(&mut arg.ty);
+    }
+    if v.lifetimes.len() == 1 { v.lifetimes.pop() } else { None }
+}
+
+/// Collects the lifetimes in a type, and the lifetime of the first reference
+/// to `Self`.
+struct CollectLifetimes {
+    lifetimes: Vec<Lifetime>,
+    self_ref: Option<Lifetime>,
+}
+
+impl VisitTypeMut for CollectLifetimes {
+    fn visit_type_reference_mut(&mut self, ty: &mut TypeReference) {
+        if self.self_ref.is_none() {
+            if let Type::Path(elem) = &*ty.elem {
+                if elem.qself.is_none() && elem.path.is_ident("Self") {
+                    self.self_ref.clone_from(&ty.lifetime);
+                }
+            }
+        }
+        walk_type_reference(self, ty);
+    }
+    fn visit_lifetime_mut(&mut self, lifetime: &mut Lifetime) {
+        self.lifetimes.push(lifetime.clone());
+    }
+    fn visit_type_bare_fn_mut(&mut self, _ty: &mut TypeBareFn) {}
+    fn visit_parenthesized_generic_arguments_mut(
+        &mut self,
+        _args: &mut ParenthesizedGenericArguments,
+    ) {
+    }
+}
+
+/// Replaces elided lifetimes in a type with `lifetime`, or records an error if
+/// there is no lifetime to replace them with.
+struct ReplaceElided<'a> {
+    lifetime: Option<&'a Lifetime>,
+    error: Option<Error>,
+}
+
+impl ReplaceElided<'_> {
+    fn replace(&mut self, span: Span) -> Option<Lifetime> {
+        match self.lifetime {
+            Some(lifetime) => Some(Lifetime { apostrophe: span, ident: lifetime.ident.clone() }),
+            None => {
+                if self.error.is_none() {
+                    self.error = Some(Error::new(
+                        span,
+                        "cannot determine the lifetime of an elided lifetime in the output",
+                    ));
+                }
+                None
+            }
+        }
+    }
+}
+
+impl VisitTypeMut for ReplaceElided<'_> {
+    fn visit_type_reference_mut(&mut self, ty: &mut TypeReference) {
+        if ty.lifetime.is_none() {
+            ty.lifetime = self.replace(ty.and_token.span);
+        }
+        walk_type_reference(self, ty);
+    }
+    fn visit_lifetime_mut(&mut self, lifetime: &mut Lifetime) {
+        if is_elided(lifetime) {
+            if let Some(new) = self.replace(lifetime.apostrophe) {
+                *lifetime = new;
+            }
+        }
+    }
+    fn visit_type_bare_fn_mut(&mut self, _ty: &mut TypeBareFn) {}
+    fn visit_parenthesized_generic_arguments_mut(
+        &mut self,
+        _args: &mut ParenthesizedGenericArguments,
+    ) {
+    }
+}
@@ -1,10 +1,20 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 macro_rules! ast_struct {
+    (
+        [$($attrs_pub:tt)*]
+        struct $name:ident #manual_extra_traits $($rest:tt)*
+    ) => {
+        #[cfg_attr(feature = "extra-traits", derive(Debug))]
+        #[cfg_attr(feature = "clone-impls", derive(Clone))]
+        $($attrs_pub)* struct $name $($rest)*
+    };
+
     (
         [$($attrs_pub:tt)*]
         struct $name:ident $($rest:tt)*
     ) => {
+        #[cfg_attr(feature = "extra-traits", derive(Debug, Eq, PartialEq, Hash))]
         #[cfg_attr(feature = "clone-impls", derive(Clone))]
         $($attrs_pub)* struct $name $($rest)*
     };
@@ -15,10 +25,20 @@ macro_rules! ast_struct {
 }
 
 macro_rules! ast_enum {
+    (
+        [$($attrs_pub:tt)*]
+        enum $name:ident #manual_extra_traits $($rest:tt)*
+    ) => (
+        #[cfg_attr(feature = "extra-traits", derive(Debug))]
+        #[cfg_attr(feature = "clone-impls", derive(Clone))]
+        $($attrs_pub)* enum $name $($rest)*
+    );
+
     (
         [$($attrs_pub:tt)*]
         enum $name:ident $($rest:tt)*
     ) => (
+        #[cfg_attr(feature = "extra-traits", derive(Debug, Eq, PartialEq, Hash))]
         #[cfg_attr(feature = "clone-impls", derive(Clone))]
         $($attrs_pub)* enum $name $($rest)*
     );
@@ -29,6 +49,14 @@ macro_rules! ast_enum {
 }
 
 macro_rules! ast_enum_of_structs {
+    (
+        $(#[$enum_attr:meta])*
+        $pub:ident $enum:ident $name:ident #manual_extra_traits $body:tt
+    ) => {
+        ast_enum!($(#[$enum_attr])* $pub $enum $name #manual_extra_traits $body);
+        ast_enum_of_structs_impl!($pub $enum $name $body);
+    };
+
     (
         $(#[$enum_attr:meta])*
         $pub:ident $enum:ident $name:ident $body:tt
@@ -52,14 +80,11 @@ macro_rules! ast_enum_of_structs_impl {
 
         $(
             $(
-                impl From<$member> for $name {
-                    fn from(e: $member) -> $name {
-                        $name::$variant(e)
-                    }
-                }
+                ast_enum_from_struct!($name::$variant, $member);
             )*
         )*
 
+        #[cfg(feature = "printing")]
         generate_to_tokens! {
             ()
             tokens
@@ -68,6 +93,20 @@ macro_rules! ast_enum_of_structs_impl {
     };
 }
 
+macro_rules! ast_enum_from_struct {
+    // No From<TokenStream> for verbatim variants.
+    ($name:ident::Verbatim, $member:ident) => {};
+
+    ($name:ident::$variant:ident, $member:ident) => {
+        impl From<$member> for $name {
+            fn from(e: $member) -> $name {
+                $name::$variant(e)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "printing")]
 macro_rules! generate_to_tokens {
     (($($arms:tt)*) $tokens:ident $name:ident { $variant:ident, $($next:tt)*}) => {
         generate_to_tokens!(
@@ -3,25 +3,39 @@
 // Based on https://github.com/dtolnay/syn/blob/2.0.37/src/item.rs.
 
 use alloc::{boxed::Box, vec::Vec};
+#[cfg(feature = "extra-traits")]
+use core::hash::{Hash, Hasher};
 
+use proc_macro2::TokenStream;
 use syn::{Attribute, Ident, Member, Path, Token, Type, punctuated::Punctuated, token};
 
-use super::PatPath;
+use super::{PatLit, PatPath};
+#[cfg(feature = "extra-traits")]
+use crate::tt::TokenStreamHelper;
 
 ast_enum_of_structs! {
     /// A pattern in a local binding, function signature, match expression, or
     /// various other places.
     #[non_exhaustive]
-    pub enum Pat {
+    pub enum Pat #manual_extra_traits {
         /// A pattern that binds a new variable: `ref mut binding @ SUBPATTERN`.
         Ident(PatIdent),
 
+        /// A literal pattern: `0`.
+        Lit(PatLit),
+
+        /// A pattern that matches any one of a set of cases.
+        Or(PatOr),
+
         /// A path pattern like `Color::Red`.
         Path(PatPath),
 
         /// A reference pattern: `&mut var`.
         Reference(PatReference),
 
+        /// The dots in a tuple or tuple struct pattern: `(a, ..)`.
+        Rest(PatRest),
+
         /// A struct or struct variant pattern: `Variant { x, y, .. }`.
         Struct(PatStruct),
 
@@ -34,6 +48,14 @@ ast_enum_of_structs! {
         /// A type ascription pattern: `foo: f64`.
         Type(PatType),
 
+        /// Tokens in pattern position not interpreted by this crate.
+        ///
+        /// The patterns of match arms and `let` statements that this crate
+        /// does not support, such as range and slice patterns, are kept as
+        /// tokens instead of failing to parse. The other parsers of `Pat`
+        /// never produce this variant.
+        Verbatim(TokenStream),
+
         /// A pattern that matches any value: `_`.
         Wild(PatWild),
     }
@@ -49,6 +71,15 @@ ast_struct! {
     }
 }
 
+ast_struct! {
+    /// A pattern that matches any one of a set of cases.
+    pub struct PatOr {
+        pub attrs: Vec<Attribute>,
+        pub leading_vert: Option<Token![|]>,
+        pub cases: Punctuated<Pat, Token![|]>,
+    }
+}
+
 ast_struct! {
     /// A reference pattern: `&mut var`.
     pub struct PatReference {
@@ -128,23 +159,107 @@ ast_struct! {
     }
 }
 
-mod parsing {
+#[cfg(feature = "extra-traits")]
+impl Eq for Pat {}
+
+#[cfg(feature = "extra-traits")]
+impl PartialEq for Pat {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Ident(this), Self::Ident(other)) => this == other,
+            (Self::Lit(this), Self::Lit(other)) => this == other,
+            (Self::Or(this), Self::Or(other)) => this == other,
+            (Self::Path(this), Self::Path(other)) => this == other,
+            (Self::Reference(this), Self::Reference(other)) => this == other,
+            (Self::Rest(this), Self::Rest(other)) => this == other,
+            (Self::Struct(this), Self::Struct(other)) => this == other,
+            (Self::Tuple(this), Self::Tuple(other)) => this == other,
+            (Self::TupleStruct(this), Self::TupleStruct(other)) => this == other,
+            (Self::Type(this), Self::Type(other)) => this == other,
+            (Self::Verbatim(this), Self::Verbatim(other)) => {
+                TokenStreamHelper(this) == TokenStreamHelper(other)
+            }
+            (Self::Wild(this), Self::Wild(other)) => this == other,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(feature = "extra-traits")]
+impl Hash for Pat {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Self::Ident(pat) => {
+                state.write_u8(0);
+                pat.hash(state);
+            }
+            Self::Lit(pat) => {
+                state.write_u8(1);
+                pat.hash(state);
+            }
+            Self::Or(pat) => {
+                state.write_u8(2);
+                pat.hash(state);
+            }
+            Self::Path(pat) => {
+                state.write_u8(3);
+                pat.hash(state);
+            }
+            Self::Reference(pat) => {
+                state.write_u8(4);
+                pat.hash(state);
+            }
+            Self::Rest(pat) => {
+                state.write_u8(5);
+                pat.hash(state);
+            }
+            Self::Struct(pat) => {
+                state.write_u8(6);
+                pat.hash(state);
+            }
+            Self::Tuple(pat) => {
+                state.write_u8(7);
+                pat.hash(state);
+            }
+            Self::TupleStruct(pat) => {
+                state.write_u8(8);
+                pat.hash(state);
+            }
+            Self::Type(pat) => {
+                state.write_u8(9);
+                pat.hash(state);
+            }
+            Self::Verbatim(pat) => {
+                state.write_u8(10);
+                TokenStreamHelper(pat).hash(state);
+            }
+            Self::Wild(pat) => {
+                state.write_u8(11);
+                pat.hash(state);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "parsing")]
+pub(crate) mod parsing {
     use alloc::{boxed::Box, vec};
 
+    use proc_macro2::TokenTree;
     use syn::{
-        Attribute, ExprPath, Ident, Member, Path, Token, braced,
+        Attribute, ExprPath, Ident, Lit, Member, Path, Token, braced,
         ext::IdentExt as _,
         parenthesized,
-        parse::{ParseStream, Result},
+        parse::{ParseStream, Result, discouraged::Speculative as _},
         punctuated::Punctuated,
         token,
     };
 
     use super::{
-        FieldPat, Pat, PatIdent, PatReference, PatRest, PatStruct, PatTuple, PatTupleStruct,
+        FieldPat, Pat, PatIdent, PatOr, PatReference, PatRest, PatStruct, PatTuple, PatTupleStruct,
         PatWild,
     };
-    use crate::path;
+    use crate::{path, verbatim};
 
     impl Pat {
         /// Parse a pattern that does _not_ involve `|` at the top level.
@@ -166,6 +281,8 @@ mod parsing {
                 pat_path_or_struct(input)
             } else if lookahead.peek(Token![_]) {
                 input.call(pat_wild).map(Pat::Wild)
+            } else if input.peek(Token![-]) || lookahead.peek(Lit) {
+                input.parse().map(Pat::Lit)
             } else if lookahead.peek(Token![ref])
                 || lookahead.peek(Token![mut])
                 || input.peek(Token![self])
@@ -176,10 +293,79 @@ mod parsing {
                 input.call(pat_reference).map(Pat::Reference)
             } else if lookahead.peek(token::Paren) {
                 input.call(pat_paren_or_tuple)
+            } else if lookahead.peek(Token![..]) && !input.peek(Token![...]) {
+                input.call(pat_rest).map(Pat::Rest)
             } else {
                 Err(lookahead.error())
             }
         }
+
+        /// Parse a pattern, possibly involving `|`, but not a leading `|`.
+        pub fn parse_multi(input: ParseStream<'_>) -> Result<Self> {
+            multi_pat_impl(input, None)
+        }
+
+        /// Parse a pattern, possibly involving `|`, possibly including a
+        /// leading `|`.
+        ///
+        /// This parser matches the behavior of the Rust 2021 edition's `$:pat`
+        /// macro_rules matcher.
+        pub fn parse_multi_with_leading_vert(input: ParseStream<'_>) -> Result<Self> {
+            let leading_vert: Option<Token![|]> = input.parse()?;
+            multi_pat_impl(input, leading_vert)
+        }
+    }
+
+    /// Parses a pattern with `parse`, or if this crate does not support it,
+    /// collects its tokens up to the next top-level token for which `is_end`
+    /// returns `true` into [`Pat::Verbatim`].
+    pub(crate) fn pat_or_verbatim(
+        input: ParseStream<'_>,
+        parse: fn(ParseStream<'_>) -> Result<Pat>,
+        is_end: fn(ParseStream<'_>) -> bool,
+    ) -> Result<Pat> {
+        let ahead = input.fork();
+        let err = match parse(&ahead) {
+            Ok(pat) if is_end(&ahead) => {
+                input.advance_to(&ahead);
+                return Ok(pat);
+            }
+            Ok(_) => None,
+            Err(e) => Some(e),
+        };
+
+        let begin = input.fork();
+        while !input.is_empty() && !is_end(input) {
+            // The `=` of `..=` does not end the pattern of a `let`.
+            if input.peek(Token![..=]) {
+                input.parse::<Token![..=]>()?;
+            } else {
+                input.parse::<TokenTree>()?;
+            }
+        }
+        let tokens = verbatim::between(&begin, input);
+        if tokens.is_empty() {
+            return Err(err.unwrap_or_else(|| input.error("expected pattern")));
+        }
+        Ok(Pat::Verbatim(tokens))
+    }
+
+    fn multi_pat_impl(input: ParseStream<'_>, leading_vert: Option<Token![|]>) -> Result<Pat> {
+        let mut pat = Pat::parse_single(input)?;
+        if leading_vert.is_some()
+            || input.peek(Token![|]) && !input.peek(Token![||]) && !input.peek(Token![|=])
+        {
+            let mut cases = Punctuated::new();
+            cases.push_value(pat);
+            while input.peek(Token![|]) && !input.peek(Token![||]) && !input.peek(Token![|=]) {
+                let punct = input.parse()?;
+                cases.push_punct(punct);
+                let pat = Pat::parse_single(input)?;
+                cases.push_value(pat);
+            }
+            pat = Pat::Or(PatOr { attrs: vec![], leading_vert, cases });
+        }
+        Ok(pat)
     }
 
     fn pat_path_or_struct(input: ParseStream<'_>) -> Result<Pat> {
@@ -198,6 +384,10 @@ mod parsing {
         Ok(PatWild { attrs: vec![], underscore_token: input.parse()? })
     }
 
+    fn pat_rest(input: ParseStream<'_>) -> Result<PatRest> {
+        Ok(PatRest { attrs: vec![], dot2_token: input.parse()? })
+    }
+
     fn pat_ident(input: ParseStream<'_>) -> Result<PatIdent> {
         Ok(PatIdent {
             attrs: vec![],
@@ -213,7 +403,7 @@ mod parsing {
 
         let mut elems = Punctuated::new();
         while !content.is_empty() {
-            let value = Pat::parse_single(&content)?;
+            let value = Pat::parse_multi_with_leading_vert(&content)?;
             elems.push_value(value);
             if content.is_empty() {
                 break;
@@ -268,7 +458,7 @@ mod parsing {
                 attrs: vec![],
                 member,
                 colon_token: Some(input.parse()?),
-                pat: Box::new(Pat::parse_single(input)?),
+                pat: Box::new(Pat::parse_multi_with_leading_vert(input)?),
             });
         }
 
@@ -293,7 +483,7 @@ mod parsing {
 
         let mut elems = Punctuated::new();
         while !content.is_empty() {
-            let value = Pat::parse_single(&content)?;
+            let value = Pat::parse_multi_with_leading_vert(&content)?;
             if content.is_empty() {
                 elems.push_value(value);
                 break;
@@ -323,14 +513,15 @@ mod parsing {
     }
 }
 
+#[cfg(feature = "printing")]
 mod printing {
     use proc_macro2::TokenStream;
     use quote::{ToTokens, TokenStreamExt as _};
     use syn::Token;
 
     use super::{
-        FieldPat, PatIdent, PatReference, PatRest, PatStruct, PatTuple, PatTupleStruct, PatType,
-        PatWild,
+        FieldPat, PatIdent, PatOr, PatReference, PatRest, PatStruct, PatTuple, PatTupleStruct,
+        PatType, PatWild,
     };
 
     impl ToTokens for PatIdent {
@@ -342,6 +533,14 @@ mod printing {
         }
     }
 
+    impl ToTokens for PatOr {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            tokens.append_all(&self.attrs);
+            self.leading_vert.to_tokens(tokens);
+            self.cases.to_tokens(tokens);
+        }
+    }
+
     impl ToTokens for PatReference {
         fn to_tokens(&self, tokens: &mut TokenStream) {
             tokens.append_all(&self.attrs);
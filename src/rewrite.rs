@@ -0,0 +1,720 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Rewriting of the token trees in function bodies.
+//
+// The body of a `Block` is kept as tokens, so the rewriters here walk the token
+// trees and treat closures, async blocks, nested items, and macro invocations
+// as separate scopes whose tokens are copied as is.
+
+use alloc::vec::Vec;
+
+use proc_macro2::{Delimiter, Group, TokenStream, TokenTree};
+use quote::{ToTokens as _, TokenStreamExt as _};
+use syn::{
+    Ident, PathArguments, Token, Type,
+    parse::{ParseStream, Parser as _, Result},
+    token,
+};
+
+use crate::{
+    Block, Stmt, generics,
+    stmt::parsing::{block_like, expr_tokens, is_block_like, is_keyword, peek_item, stmt_item},
+};
+
+impl Block {
+    /// Rewrites the values returned from the function whose body is this
+    /// block.
+    ///
+    /// `f` is called with the operand of each `return` expression and with
+    /// the trailing expression of the block, and the tokens it returns replace
+    /// them. For a bare `return` and for a block without a trailing
+    /// expression, `f` is called with an empty token stream, and its result
+    /// becomes the operand of the `return` or the trailing expression of the
+    /// block. (No trailing expression is added if the last statement is a
+    /// `return` expression.)
+    ///
+    /// `return`s inside closures, async blocks, nested items, and macro
+    /// invocations belong to a different function or cannot be seen without
+    /// expanding the macro, and are left alone.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quote::quote;
+    /// use syn_mid::Block;
+    ///
+    /// let mut block: Block = syn::parse_quote!({
+    ///     if x < 0 {
+    ///         return -1;
+    ///     }
+    ///     x
+    /// });
+    /// block.rewrite_returns(|expr| quote!(Ok(#expr))).unwrap();
+    /// assert_eq!(
+    ///     quote!(#block).to_string(),
+    ///     quote!({ if x < 0 { return Ok(-1); } Ok(x) }).to_string(),
+    /// );
+    /// ```
+    pub fn rewrite_returns<F>(&mut self, mut f: F) -> Result<()>
+    where
+        F: FnMut(TokenStream) -> TokenStream,
+    {
+        let stmts = self.split_stmts()?;
+        let has_tail = match stmts.last() {
+            Some(Stmt::Expr(expr, None)) => !expr.is_empty(),
+            Some(Stmt::Macro(mac)) => mac.semi_token.is_none(),
+            _ => false,
+        };
+        let ends_with_return = match stmts.last() {
+            Some(Stmt::Expr(expr, _)) => starts_with_return.parse2(expr.clone())?,
+            _ => false,
+        };
+
+        let mut tokens = TokenStream::new();
+        for (i, stmt) in stmts.iter().enumerate() {
+            let stmt = stmt.to_token_stream();
+            let rewritten =
+                (|input: ParseStream<'_>| returns(input, &mut f)).parse2(stmt.clone())?;
+            if has_tail && i == stmts.len() - 1 && !ends_with_return {
+                tokens.extend(f(rewritten));
+            } else {
+                tokens.extend(rewritten);
+            }
+        }
+        if !has_tail && !ends_with_return {
+            tokens.extend(f(TokenStream::new()));
+        }
+        self.stmts = tokens;
+        Ok(())
+    }
+
+    /// Rewrites the operands of the `?` operators in the function whose body
+    /// is this block.
+    ///
+    /// `f` is called with the receiver of each `?`, and the tokens it returns
+    /// replace it. The receiver is the postfix expression that `?` applies
+    /// to: `b.c()` in `a + b.c()?`, and `x.await` in `&x.await?`. The
+    /// replacement is used as is, so it should be a postfix expression, a
+    /// path, a call, or a parenthesized expression.
+    ///
+    /// `?`s inside closures, async blocks, and nested items are left alone,
+    /// since they do not return from the function. The arguments of macro
+    /// invocations are treated as comma-separated expressions
+    /// (`println!("{}", x?)`), except in item-like macros such as
+    /// `macro_rules!`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quote::quote;
+    /// use syn_mid::Block;
+    ///
+    /// let mut block: Block = syn::parse_quote!({
+    ///     let n = s.parse::<u8>()? + 1;
+    ///     Ok(n)
+    /// });
+    /// block.rewrite_try_exprs(|expr| quote!(#expr.map_err(|e| ctx(e)))).unwrap();
+    /// assert_eq!(
+    ///     quote!(#block).to_string(),
+    ///     quote!({ let n = s.parse::<u8>().map_err(|e| ctx(e))? + 1; Ok(n) }).to_string(),
+    /// );
+    /// ```
+    pub fn rewrite_try_exprs<F>(&mut self, mut f: F) -> Result<()>
+    where
+        F: FnMut(TokenStream) -> TokenStream,
+    {
+        let construct = false;
+        self.stmts = (|input: ParseStream<'_>| try_exprs(input, construct, &mut f))
+            .parse2(self.stmts.clone())?;
+        Ok(())
+    }
+
+    /// Replaces `Self` and generic type parameters with the given types in
+    /// this block.
+    ///
+    /// This is the counterpart of
+    /// [`Signature::substitute`](crate::Signature::substitute) for the
+    /// function body. An identifier is replaced if it is one of the names in
+    /// `substs` and is not part of a longer path (`module::T`), a field or
+    /// method name (`x.T`), a lifetime, a macro name, a field name in a
+    /// struct expression or pattern (`S { T: 0 }`, `S { T }`), or the name of
+    /// a named macro argument (`format!("{T}", T = 1)`).
+    ///
+    /// A path type is written in the turbofish form, so that it is also valid
+    /// in expressions and patterns: `T { a: 1 }` and `T::A(x)` become
+    /// `Foo::<u8> { a: 1 }` and `Foo::<u8>::A(x)`. Other types are written
+    /// as `<Type>::` if followed by `::`, so that `T::new()` becomes
+    /// `<[u8; 4]>::new()`. A qualified path (`<X as Trait>::Assoc`) cannot
+    /// be used as a struct name or a pattern without an unstable feature.
+    ///
+    /// Nested items of any kind (`fn`, `struct`, `enum`, `type`, `impl`, and
+    /// so on) have their own generics and `Self`, and are left alone. The
+    /// arguments of macro invocations are treated as expressions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quote::quote;
+    /// use syn_mid::Block;
+    ///
+    /// let mut block: Block = syn::parse_quote!({
+    ///     let v: Vec<T> = Vec::new();
+    ///     T::from_iter(v)
+    /// });
+    /// block.substitute(&[(syn::parse_quote!(T), syn::parse_quote!(String))]).unwrap();
+    /// assert_eq!(
+    ///     quote!(#block).to_string(),
+    ///     quote!({ let v: Vec<String> = Vec::new(); String::from_iter(v) }).to_string(),
+    /// );
+    /// ```
+    pub fn substitute(&mut self, substs: &[(Ident, Type)]) -> Result<()> {
+        let braced = true;
+        self.stmts = (|input: ParseStream<'_>| substitute(input, substs, braced))
+            .parse2(self.stmts.clone())?;
+        Ok(())
+    }
+}
+
+fn starts_with_return(input: ParseStream<'_>) -> Result<bool> {
+    let ret = input.peek(Token![return]);
+    input.parse::<TokenStream>()?;
+    Ok(ret)
+}
+
+fn returns<F>(input: ParseStream<'_>, f: &mut F) -> Result<TokenStream>
+where
+    F: FnMut(TokenStream) -> TokenStream,
+{
+    walk(input, &mut |input, tokens| {
+        if input.peek(Token![return]) {
+            input.parse::<Token![return]>()?.to_tokens(tokens);
+            let mut expr = TokenStream::new();
+            expr_tokens(input, &mut expr)?;
+            let expr = (|input: ParseStream<'_>| returns(input, f)).parse2(expr)?;
+            tokens.extend(f(expr));
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    })
+}
+
+/// Rewrites the receivers of `?` in `input`.
+///
+/// If `construct` is `true`, the input is a single block-like expression (see
+/// [`is_block_like`]) and its leading keyword is not treated as the start of
+/// another one.
+fn try_exprs<F>(input: ParseStream<'_>, construct: bool, f: &mut F) -> Result<TokenStream>
+where
+    F: FnMut(TokenStream) -> TokenStream,
+{
+    let mut tokens: Vec<TokenTree> = Vec::new();
+    // The start of the postfix expression that ends at the current position
+    // (`a.b::<T>()[0]` in `x + a.b::<T>()[0]`), if any.
+    let mut start: Option<usize> = None;
+    // Whether the last token ends an operand, so that a group or `?` after it
+    // is a postfix operator.
+    let mut operand_end = false;
+    // Whether the last token is `.` or `::`, so that an identifier after it
+    // continues the postfix expression.
+    let mut after_dot = false;
+    // Whether the last tokens are a block-like expression, which only `.` and
+    // `?` continue: `if c {} (a)?` is two expressions.
+    let mut after_block_like = false;
+    let mut prev: Option<TokenTree> = None;
+    let mut first = true;
+    while !input.is_empty() {
+        if after_block_like
+            && !(input.peek(Token![.]) && !input.peek(Token![..]) || input.peek(Token![?]))
+        {
+            start = None;
+            operand_end = false;
+        }
+        after_block_like = false;
+        let block_like_start = !operand_end && !(construct && first);
+        first = false;
+
+        let mut scope = TokenStream::new();
+        let prev2 = tokens.len().checked_sub(2).map(|i| &tokens[i]);
+        if let Some(kind) = other_scope(input, [prev2, prev.as_ref()], &mut scope)? {
+            if let Scope::Macro = kind {
+                scope = macro_args(scope, f)?;
+            }
+            let i = tokens.len();
+            prev = scope.clone().into_iter().last();
+            tokens.extend(scope);
+            match kind {
+                Scope::AsyncBlock | Scope::Macro => {
+                    // `a::b!()` starts at `a`.
+                    if !after_dot {
+                        start = Some(i);
+                    }
+                    operand_end = true;
+                }
+                Scope::Closure | Scope::Item => {
+                    start = None;
+                    operand_end = false;
+                }
+            }
+            after_dot = false;
+            continue;
+        }
+
+        if block_like_start && is_block_like(input)? {
+            let mut construct = TokenStream::new();
+            block_like(input, &mut construct)?;
+            let i = tokens.len();
+            let construct =
+                (|input: ParseStream<'_>| try_exprs(input, true, f)).parse2(construct)?;
+            tokens.extend(construct);
+            prev = tokens.last().cloned();
+            start = Some(i);
+            operand_end = true;
+            after_dot = false;
+            after_block_like = true;
+            continue;
+        }
+
+        if input.peek(Token![?]) {
+            let question: Token![?] = input.parse()?;
+            if let Some(s) = start.filter(|_| operand_end) {
+                let receiver: TokenStream = tokens.drain(s..).collect();
+                tokens.extend(f(receiver));
+            }
+            tokens.extend(question.into_token_stream());
+            prev = tokens.last().cloned();
+            after_dot = false;
+            continue;
+        }
+
+        // generic arguments: `::<T>` and `<T as Trait>::`
+        if input.peek(Token![<])
+            && !input.peek(Token![<=])
+            && !input.peek(Token![<<=])
+            && (after_dot || !operand_end)
+        {
+            if !after_dot {
+                start = Some(tokens.len());
+            }
+            let mut depth = 0_usize;
+            let mut prev_char = None;
+            loop {
+                let tt: TokenTree = input.parse()?;
+                let c = match &tt {
+                    TokenTree::Punct(p) => Some(p.as_char()),
+                    _ => None,
+                };
+                match c {
+                    Some('<') => depth += 1,
+                    Some('>') if !matches!(prev_char, Some('-' | '=')) => {
+                        depth = depth.saturating_sub(1);
+                    }
+                    _ => {}
+                }
+                prev_char = c;
+                tokens.push(tt);
+                if depth == 0 || input.is_empty() {
+                    break;
+                }
+            }
+            prev = tokens.last().cloned();
+            operand_end = true;
+            after_dot = false;
+            continue;
+        }
+
+        if input.peek(Token![..]) {
+            tokens.extend(input.parse::<Token![..]>()?.into_token_stream());
+            prev = tokens.last().cloned();
+            start = None;
+            operand_end = false;
+            after_dot = false;
+            continue;
+        }
+
+        if input.peek(Token![::]) || input.peek(Token![.]) {
+            if input.peek(Token![::]) {
+                if !operand_end {
+                    // leading `::` of a path
+                    start = Some(tokens.len());
+                }
+                tokens.extend(input.parse::<Token![::]>()?.into_token_stream());
+            } else {
+                tokens.extend(input.parse::<Token![.]>()?.into_token_stream());
+            }
+            prev = tokens.last().cloned();
+            operand_end = false;
+            after_dot = true;
+            continue;
+        }
+
+        let tt: TokenTree = input.parse()?;
+        let i = tokens.len();
+        match &tt {
+            TokenTree::Group(g) => {
+                let construct = false;
+                let stream =
+                    (|input: ParseStream<'_>| try_exprs(input, construct, f)).parse2(g.stream())?;
+                let mut group = Group::new(g.delimiter(), stream);
+                group.set_span(g.span());
+                tokens.push(group.into());
+                if !operand_end {
+                    start = Some(i);
+                }
+                operand_end = true;
+            }
+            TokenTree::Ident(ident) if after_dot && ident == "await" => {
+                tokens.push(tt.clone());
+                operand_end = true;
+            }
+            // the label of `break 'a` and `continue 'a`
+            TokenTree::Ident(_) if matches!(&prev, Some(TokenTree::Punct(p)) if p.as_char() == '\'') =>
+            {
+                tokens.push(tt.clone());
+                start = None;
+                operand_end = false;
+            }
+            TokenTree::Ident(ident) if is_keyword(ident) => {
+                tokens.push(tt.clone());
+                start = None;
+                operand_end = false;
+            }
+            TokenTree::Ident(_) | TokenTree::Literal(_) => {
+                tokens.push(tt.clone());
+                if !after_dot {
+                    start = Some(i);
+                }
+                operand_end = true;
+            }
+            TokenTree::Punct(_) => {
+                tokens.push(tt.clone());
+                start = None;
+                operand_end = false;
+            }
+        }
+        after_dot = false;
+        prev = Some(tt);
+    }
+    Ok(tokens.into_iter().collect())
+}
+
+/// Replaces the names in `substs` in `input`, skipping nested items.
+/// `braced` is `true` if `input` is enclosed in braces.
+fn substitute(
+    input: ParseStream<'_>,
+    substs: &[(Ident, Type)],
+    braced: bool,
+) -> Result<TokenStream> {
+    let mut tokens = TokenStream::new();
+    let mut prev = [None, None];
+    while !input.is_empty() {
+        let mut scope = TokenStream::new();
+        if let Some(kind) = other_scope(input, [prev[0].as_ref(), prev[1].as_ref()], &mut scope)? {
+            prev = [None, scope.clone().into_iter().last()];
+            match kind {
+                Scope::Item => tokens.extend(scope),
+                Scope::Closure | Scope::AsyncBlock | Scope::Macro => {
+                    // The scope starts with a closure, `async`, or a macro
+                    // name, so it must not be detected again.
+                    let mut prev = [None, None];
+                    let braced = false;
+                    let scope = (|input: ParseStream<'_>| {
+                        let mut tokens = TokenStream::new();
+                        while !input.is_empty() {
+                            substitute_tt(input, substs, braced, &mut prev, &mut tokens)?;
+                        }
+                        Ok(tokens)
+                    })
+                    .parse2(scope)?;
+                    tokens.extend(scope);
+                }
+            }
+            continue;
+        }
+        substitute_tt(input, substs, braced, &mut prev, &mut tokens)?;
+    }
+    Ok(tokens)
+}
+
+/// Copies the next token tree of `input`, replacing it if it is one of the
+/// names in `substs`. `prev` holds the last two token trees.
+fn substitute_tt(
+    input: ParseStream<'_>,
+    substs: &[(Ident, Type)],
+    braced: bool,
+    prev: &mut [Option<TokenTree>; 2],
+    tokens: &mut TokenStream,
+) -> Result<()> {
+    let tt: TokenTree = input.parse()?;
+    match &tt {
+        TokenTree::Group(g) => {
+            let braced = g.delimiter() == Delimiter::Brace;
+            let stream =
+                (|input: ParseStream<'_>| substitute(input, substs, braced)).parse2(g.stream())?;
+            let mut group = Group::new(g.delimiter(), stream);
+            group.set_span(g.span());
+            tokens.append(group);
+        }
+        TokenTree::Ident(ident) => {
+            let punct = |tt: &Option<TokenTree>| match tt {
+                Some(TokenTree::Punct(p)) => Some(p.as_char()),
+                _ => None,
+            };
+            let after_path_sep = punct(&prev[0]) == Some(':') && punct(&prev[1]) == Some(':');
+            let after_dot_or_quote = matches!(punct(&prev[1]), Some('.' | '\''));
+            let before_bang = input.peek(Token![!]) && !input.peek(Token![!=]);
+            let before_colon = input.peek(Token![:]) && !input.peek(Token![::]);
+            // A named macro argument (`T = 1`) or a shorthand field in a
+            // struct expression or pattern (`S { T }`). `Self` is a keyword
+            // and cannot be a field name.
+            let first = prev[1].is_none() || punct(&prev[1]) == Some(',');
+            let named_arg = first
+                && input.peek(Token![=])
+                && !input.peek(Token![==])
+                && !input.peek(Token![=>]);
+            let shorthand =
+                braced && first && ident != "Self" && (input.is_empty() || input.peek(Token![,]));
+            let skip = after_path_sep
+                || after_dot_or_quote
+                || before_bang
+                || before_colon
+                || named_arg
+                || shorthand;
+            match substs.iter().find(|(name, _)| name == ident) {
+                Some((_, ty)) if !skip => {
+                    match generics::clone_type(ty) {
+                        // Paths are written in the turbofish form, which is
+                        // also valid in expressions and patterns:
+                        // `Foo::<u8> { a: 1 }`, `Foo::<u8>::A(x)`.
+                        Type::Path(mut ty) if ty.qself.is_none() => {
+                            for segment in &mut ty.path.segments {
+                                if let PathArguments::AngleBracketed(args) = &mut segment.arguments
+                                {
+                                    args.colon2_token.get_or_insert(Token![::](ident.span()));
+                                }
+                            }
+                            ty.to_tokens(tokens);
+                        }
+                        ty => {
+                            let ty = generics::parenthesize(ty, ident.span());
+                            if input.peek(Token![::]) {
+                                tokens.extend(quote::quote_spanned!(ident.span()=> <#ty>));
+                            } else {
+                                ty.to_tokens(tokens);
+                            }
+                        }
+                    }
+                }
+                _ => tokens.append(tt.clone()),
+            }
+        }
+        _ => tokens.append(tt.clone()),
+    }
+    *prev = [prev[1].take(), Some(tt)];
+    Ok(())
+}
+
+/// Walks the token trees of `input` and its groups, calling `visit` at each
+/// position in the scope of the current function.
+///
+/// `visit` returns `true` if it has consumed tokens from the input and
+/// appended their replacement to the output; otherwise, the next token tree
+/// is copied (recursing into groups). Closures, async blocks, nested items,
+/// and macro invocations are copied without calling `visit`.
+fn walk<V>(input: ParseStream<'_>, visit: &mut V) -> Result<TokenStream>
+where
+    V: FnMut(ParseStream<'_>, &mut TokenStream) -> Result<bool>,
+{
+    let mut tokens = TokenStream::new();
+    let mut prev: [Option<TokenTree>; 2] = [None, None];
+    while !input.is_empty() {
+        let mut scope = TokenStream::new();
+        if other_scope(input, [prev[0].as_ref(), prev[1].as_ref()], &mut scope)?.is_some() {
+            prev = [None, scope.clone().into_iter().last()];
+            tokens.extend(scope);
+            continue;
+        }
+        if visit(input, &mut tokens)? {
+            prev = [None, None];
+            continue;
+        }
+        let tt: TokenTree = input.parse()?;
+        match &tt {
+            TokenTree::Group(g) => {
+                let stream = (|input: ParseStream<'_>| walk(input, visit)).parse2(g.stream())?;
+                let mut group = Group::new(g.delimiter(), stream);
+                group.set_span(g.span());
+                tokens.append(group);
+            }
+            _ => tokens.append(tt.clone()),
+        }
+        prev = [prev[1].take(), Some(tt)];
+    }
+    Ok(tokens)
+}
+
+/// Rewrites the receivers of `?` in the arguments of the macro invocation
+/// `mac`.
+///
+/// The arguments are treated as comma-separated expressions, as in
+/// `println!("{}", x?)` and `vec![a?; n]`. Item-like macros
+/// (`macro_rules! m { ... }`) are left alone, since `?` is a repetition
+/// operator in macro definitions.
+fn macro_args<F>(mac: TokenStream, f: &mut F) -> Result<TokenStream>
+where
+    F: FnMut(TokenStream) -> TokenStream,
+{
+    let mut tts: Vec<TokenTree> = mac.into_iter().collect();
+    let n = tts.len();
+    if n >= 2 && matches!(&tts[n - 2], TokenTree::Punct(p) if p.as_char() == '!') {
+        if let TokenTree::Group(group) = &tts[n - 1] {
+            let construct = false;
+            let stream =
+                (|input: ParseStream<'_>| try_exprs(input, construct, f)).parse2(group.stream())?;
+            let mut args = Group::new(group.delimiter(), stream);
+            args.set_span(group.span());
+            tts[n - 1] = TokenTree::Group(args);
+        }
+    }
+    Ok(tts.into_iter().collect())
+}
+
+/// A part of a function body that is not in the scope of the function itself.
+#[derive(Clone, Copy)]
+enum Scope {
+    /// A closure or an async closure.
+    Closure,
+    /// An async block.
+    AsyncBlock,
+    /// A nested item.
+    Item,
+    /// A macro invocation.
+    Macro,
+}
+
+/// Copies a closure, async block, nested item, or macro invocation if the
+/// input starts with one. `prev` holds the last two token trees.
+fn other_scope(
+    input: ParseStream<'_>,
+    prev: [Option<&TokenTree>; 2],
+    tokens: &mut TokenStream,
+) -> Result<Option<Scope>> {
+    // async blocks and async closures
+    if input.peek(Token![async])
+        && (input.peek2(token::Brace)
+            || input.peek2(Token![move])
+            || input.peek2(Token![|])
+            || input.peek2(Token![||]))
+    {
+        input.parse::<Token![async]>()?.to_tokens(tokens);
+        if input.peek(Token![move]) {
+            input.parse::<Token![move]>()?.to_tokens(tokens);
+        }
+        if input.peek(token::Brace) {
+            tokens.append(input.parse::<TokenTree>()?);
+            return Ok(Some(Scope::AsyncBlock));
+        }
+        closure(input, tokens)?;
+        return Ok(Some(Scope::Closure));
+    }
+    if (input.peek(Token![move]) || input.peek(Token![static]))
+        && (input.peek2(Token![|]) || input.peek2(Token![||]))
+    {
+        tokens.append(input.parse::<TokenTree>()?);
+        closure(input, tokens)?;
+        return Ok(Some(Scope::Closure));
+    }
+    if (input.peek(Token![|]) || input.peek(Token![||])) && is_expr_start(prev) {
+        closure(input, tokens)?;
+        return Ok(Some(Scope::Closure));
+    }
+
+    // nested items, but not `'static`, `*const T`, or `&raw const x`
+    let in_type = match prev[1] {
+        Some(TokenTree::Punct(p)) => p.as_char() == '\'' || p.as_char() == '*',
+        Some(TokenTree::Ident(ident)) => ident == "raw",
+        _ => false,
+    };
+    if !in_type && peek_item(input) {
+        stmt_item(input, tokens)?;
+        return Ok(Some(Scope::Item));
+    }
+
+    // macro invocations: `m!(...)`, `macro_rules! m { ... }`
+    if input.peek(Ident) && input.peek2(Token![!]) && !input.peek2(Token![!=]) {
+        let ahead = input.fork();
+        ahead.parse::<TokenTree>()?;
+        ahead.parse::<TokenTree>()?;
+        if ahead.peek(Ident) {
+            ahead.parse::<TokenTree>()?;
+        }
+        if ahead.peek(token::Paren) || ahead.peek(token::Bracket) || ahead.peek(token::Brace) {
+            while !input.peek(token::Paren)
+                && !input.peek(token::Bracket)
+                && !input.peek(token::Brace)
+            {
+                tokens.append(input.parse::<TokenTree>()?);
+            }
+            tokens.append(input.parse::<TokenTree>()?);
+            return Ok(Some(Scope::Macro));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Returns `true` if a `|` after the token trees `prev` starts a closure
+/// rather than being a binary operator or separating the cases of an
+/// or-pattern.
+fn is_expr_start(prev: [Option<&TokenTree>; 2]) -> bool {
+    match prev[1] {
+        None => true,
+        Some(TokenTree::Ident(ident)) => {
+            ident == "return" || ident == "break" || ident == "in" || ident == "yield"
+        }
+        // `?` is a postfix operator, and `|` is the first half of `||`.
+        Some(TokenTree::Punct(p)) => p.as_char() != '?' && p.as_char() != '|',
+        // A block, but not the fields of a struct expression or pattern
+        // (`S { x } | T`) or a match arm (`P => {} | Q => {}`).
+        Some(TokenTree::Group(g)) => {
+            g.delimiter() == proc_macro2::Delimiter::Brace
+                && match prev[0] {
+                    Some(TokenTree::Ident(ident)) => is_keyword(ident),
+                    Some(TokenTree::Punct(p)) => p.as_char() != '>',
+                    _ => true,
+                }
+        }
+        Some(TokenTree::Literal(_)) => false,
+    }
+}
+
+/// Copies a closure starting at its parameter list.
+fn closure(input: ParseStream<'_>, tokens: &mut TokenStream) -> Result<()> {
+    if input.peek(Token![||]) {
+        input.parse::<Token![||]>()?.to_tokens(tokens);
+    } else {
+        input.parse::<Token![|]>()?.to_tokens(tokens);
+        while !input.peek(Token![|]) {
+            tokens.append(input.parse::<TokenTree>()?);
+        }
+        input.parse::<Token![|]>()?.to_tokens(tokens);
+    }
+    if input.peek(Token![->]) {
+        while !input.peek(token::Brace) {
+            tokens.append(input.parse::<TokenTree>()?);
+        }
+    }
+    if input.peek(token::Brace) {
+        tokens.append(input.parse::<TokenTree>()?);
+        return Ok(());
+    }
+    if is_block_like(input)? {
+        let mut block = TokenStream::new();
+        block_like(input, &mut block)?;
+        tokens.extend(block);
+        if !(input.peek(Token![.]) || input.peek(Token![?])) {
+            return Ok(());
+        }
+    }
+    expr_tokens(input, tokens)
+}
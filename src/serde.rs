@@ -0,0 +1,986 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Serialize and Deserialize impls, in the data format of https://github.com/taiki-e/syn-serde.
+//
+// Each node is converted to and from an owned adapter type that derives
+// these traits. The data structures of syn and the tokens that are not parsed
+// are represented as their token text, and parsed again on deserialization.
+// This differs from syn-serde, which serializes the data structures of syn
+// structurally: its adapter types require the "full" feature of syn, which
+// this crate exists to avoid.
+
+use alloc::{
+    boxed::Box,
+    string::{String, ToString as _},
+    vec::Vec,
+};
+
+use proc_macro2::{Span, TokenStream};
+use quote::ToTokens;
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error as _};
+use syn::{
+    Attribute, Ident, ReturnType, Token,
+    ext::IdentExt as _,
+    parse::{Parse, Parser as _, Result},
+    punctuated::Punctuated,
+    token,
+};
+
+trait Repr {
+    type Node;
+
+    fn into_node(self) -> Result<Self::Node>;
+}
+
+macro_rules! impl_serde {
+    ($($ty:ident,)*) => {$(
+        impl Serialize for crate::$ty {
+            fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                $ty::from(self).serialize(serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for crate::$ty {
+            fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                $ty::deserialize(deserializer)?.into_node().map_err(D::Error::custom)
+            }
+        }
+    )*};
+}
+
+impl_serde! {
+    ItemFn,
+    Block,
+    Signature,
+    FnArg,
+    Receiver,
+    Variadic,
+    Pat,
+    PatIdent,
+    PatOr,
+    PatReference,
+    PatRest,
+    PatStruct,
+    PatTuple,
+    PatTupleStruct,
+    PatType,
+    PatWild,
+    FieldPat,
+    ExprMatch,
+    Arm,
+    Stmt,
+    Local,
+    LocalInit,
+    StmtMacro,
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn not(b: &bool) -> bool {
+    !*b
+}
+
+fn text<T: ToTokens>(node: &T) -> String {
+    node.to_token_stream().to_string()
+}
+
+fn parse<T: Parse>(s: &str) -> Result<T> {
+    syn::parse_str(s)
+}
+
+fn attrs_text(attrs: &[Attribute]) -> Vec<String> {
+    attrs.iter().map(text).collect()
+}
+
+fn parse_attrs(attrs: &[String]) -> Result<Vec<Attribute>> {
+    let mut v = Vec::with_capacity(attrs.len());
+    for attr in attrs {
+        v.extend(Attribute::parse_outer.parse_str(attr)?);
+    }
+    Ok(v)
+}
+
+fn parse_inner_attrs(attrs: &[String]) -> Result<Vec<Attribute>> {
+    let mut v = Vec::with_capacity(attrs.len());
+    for attr in attrs {
+        v.extend(Attribute::parse_inner.parse_str(attr)?);
+    }
+    Ok(v)
+}
+
+fn parse_ident(s: &str) -> Result<Ident> {
+    Ident::parse_any.parse_str(s)
+}
+
+fn parse_punctuated<R, P>(elems: Vec<R>, trailing_comma: bool) -> Result<Punctuated<R::Node, P>>
+where
+    R: Repr,
+    P: Default,
+{
+    let mut punctuated =
+        elems.into_iter().map(R::into_node).collect::<Result<Punctuated<_, _>>>()?;
+    if trailing_comma && !punctuated.empty_or_trailing() {
+        punctuated.push_punct(P::default());
+    }
+    Ok(punctuated)
+}
+
+fn into_boxed_node<R: Repr>(repr: R) -> Result<Box<R::Node>> {
+    repr.into_node().map(Box::new)
+}
+
+// -----------------------------------------------------------------------------
+// Function
+
+#[derive(Serialize, Deserialize)]
+struct ItemFn {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    attrs: Vec<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    vis: String,
+    sig: Signature,
+    block: Block,
+}
+
+impl From<&crate::ItemFn> for ItemFn {
+    fn from(node: &crate::ItemFn) -> Self {
+        Self {
+            attrs: attrs_text(&node.attrs),
+            vis: text(&node.vis),
+            sig: (&node.sig).into(),
+            block: (&*node.block).into(),
+        }
+    }
+}
+
+impl Repr for ItemFn {
+    type Node = crate::ItemFn;
+
+    fn into_node(self) -> Result<Self::Node> {
+        Ok(crate::ItemFn {
+            attrs: parse_attrs(&self.attrs)?,
+            vis: parse(&self.vis)?,
+            sig: self.sig.into_node()?,
+            block: Box::new(self.block.into_node()?),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Block {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    inner_attrs: Vec<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    stmts: String,
+}
+
+impl From<&crate::Block> for Block {
+    fn from(node: &crate::Block) -> Self {
+        Self { inner_attrs: attrs_text(&node.inner_attrs), stmts: node.stmts.to_string() }
+    }
+}
+
+impl Repr for Block {
+    type Node = crate::Block;
+
+    fn into_node(self) -> Result<Self::Node> {
+        let span = Span::call_site();
+        Ok(crate::Block {
+            brace_token: token::Brace(span),
+            inner_attrs: parse_inner_attrs(&self.inner_attrs)?,
+            stmts: parse::<TokenStream>(&self.stmts)?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Signature {
+    #[serde(rename = "const", default, skip_serializing_if = "not")]
+    constness: bool,
+    #[serde(rename = "async", default, skip_serializing_if = "not")]
+    asyncness: bool,
+    #[serde(rename = "unsafe", default, skip_serializing_if = "not")]
+    unsafety: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    abi: Option<String>,
+    ident: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    generics: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    where_clause: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    inputs: Vec<FnArg>,
+    #[serde(default, skip_serializing_if = "not")]
+    trailing_comma: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    variadic: Option<Variadic>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    output: Option<String>,
+}
+
+impl From<&crate::Signature> for Signature {
+    fn from(node: &crate::Signature) -> Self {
+        Self {
+            constness: node.constness.is_some(),
+            asyncness: node.asyncness.is_some(),
+            unsafety: node.unsafety.is_some(),
+            abi: node.abi.as_ref().map(text),
+            ident: node.ident.to_string(),
+            generics: text(&node.generics),
+            where_clause: node.generics.where_clause.as_ref().map(text),
+            inputs: node.inputs.iter().map(FnArg::from).collect(),
+            trailing_comma: node.inputs.trailing_punct(),
+            variadic: node.variadic.as_ref().map(Variadic::from),
+            output: match &node.output {
+                ReturnType::Default => None,
+                ReturnType::Type(_, ty) => Some(text(ty)),
+            },
+        }
+    }
+}
+
+impl Repr for Signature {
+    type Node = crate::Signature;
+
+    fn into_node(self) -> Result<Self::Node> {
+        let span = Span::call_site();
+        let mut generics: syn::Generics = parse(&self.generics)?;
+        generics.where_clause = self.where_clause.as_deref().map(parse).transpose()?;
+        Ok(crate::Signature {
+            constness: self.constness.then(|| Token![const](span)),
+            asyncness: self.asyncness.then(|| Token![async](span)),
+            unsafety: self.unsafety.then(|| Token![unsafe](span)),
+            abi: self.abi.as_deref().map(parse).transpose()?,
+            fn_token: Token![fn](span),
+            ident: parse_ident(&self.ident)?,
+            generics,
+            paren_token: token::Paren(span),
+            inputs: parse_punctuated(self.inputs, self.trailing_comma)?,
+            variadic: self.variadic.map(Variadic::into_node).transpose()?,
+            output: match self.output {
+                None => ReturnType::Default,
+                Some(ty) => ReturnType::Type(Token![->](span), Box::new(parse(&ty)?)),
+            },
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum FnArg {
+    Receiver(Receiver),
+    Typed(PatType),
+}
+
+impl From<&crate::FnArg> for FnArg {
+    fn from(node: &crate::FnArg) -> Self {
+        match node {
+            crate::FnArg::Receiver(arg) => Self::Receiver(arg.into()),
+            crate::FnArg::Typed(arg) => Self::Typed(arg.into()),
+        }
+    }
+}
+
+impl Repr for FnArg {
+    type Node = crate::FnArg;
+
+    fn into_node(self) -> Result<Self::Node> {
+        Ok(match self {
+            Self::Receiver(arg) => crate::FnArg::Receiver(arg.into_node()?),
+            Self::Typed(arg) => crate::FnArg::Typed(arg.into_node()?),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Receiver {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    attrs: Vec<String>,
+    #[serde(rename = "ref", default, skip_serializing_if = "not")]
+    reference: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    lifetime: Option<String>,
+    #[serde(rename = "mut", default, skip_serializing_if = "not")]
+    mutability: bool,
+    #[serde(default, skip_serializing_if = "not")]
+    colon_token: bool,
+    ty: String,
+}
+
+impl From<&crate::Receiver> for Receiver {
+    fn from(node: &crate::Receiver) -> Self {
+        Self {
+            attrs: attrs_text(&node.attrs),
+            reference: node.reference.is_some(),
+            lifetime: node.reference.as_ref().and_then(|(_, lifetime)| lifetime.as_ref()).map(text),
+            mutability: node.mutability.is_some(),
+            colon_token: node.colon_token.is_some(),
+            ty: text(&node.ty),
+        }
+    }
+}
+
+impl Repr for Receiver {
+    type Node = crate::Receiver;
+
+    fn into_node(self) -> Result<Self::Node> {
+        let span = Span::call_site();
+        let lifetime = self.lifetime.as_deref().map(parse).transpose()?;
+        Ok(crate::Receiver {
+            attrs: parse_attrs(&self.attrs)?,
+            reference: (self.reference || lifetime.is_some()).then(|| (Token![&](span), lifetime)),
+            mutability: self.mutability.then(|| Token![mut](span)),
+            self_token: Token![self](span),
+            colon_token: self.colon_token.then(|| Token![:](span)),
+            ty: Box::new(parse(&self.ty)?),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Variadic {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    attrs: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pat: Option<Box<Pat>>,
+    #[serde(default, skip_serializing_if = "not")]
+    comma: bool,
+}
+
+impl From<&crate::Variadic> for Variadic {
+    fn from(node: &crate::Variadic) -> Self {
+        Self {
+            attrs: attrs_text(&node.attrs),
+            pat: node.pat.as_ref().map(|(pat, _)| Box::new((&**pat).into())),
+            comma: node.comma.is_some(),
+        }
+    }
+}
+
+impl Repr for Variadic {
+    type Node = crate::Variadic;
+
+    fn into_node(self) -> Result<Self::Node> {
+        let span = Span::call_site();
+        Ok(crate::Variadic {
+            attrs: parse_attrs(&self.attrs)?,
+            pat: match self.pat {
+                Some(pat) => Some((into_boxed_node(*pat)?, Token![:](span))),
+                None => None,
+            },
+            dots: Token![...](span),
+            comma: self.comma.then(|| Token![,](span)),
+        })
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Pattern
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Pat {
+    Ident(PatIdent),
+    Lit(String),
+    Or(PatOr),
+    Path(String),
+    Reference(PatReference),
+    Rest(PatRest),
+    Struct(PatStruct),
+    Tuple(PatTuple),
+    TupleStruct(PatTupleStruct),
+    Type(PatType),
+    Verbatim(String),
+    Wild(PatWild),
+}
+
+impl From<&crate::Pat> for Pat {
+    fn from(node: &crate::Pat) -> Self {
+        match node {
+            crate::Pat::Ident(pat) => Self::Ident(pat.into()),
+            crate::Pat::Lit(pat) => Self::Lit(text(pat)),
+            crate::Pat::Or(pat) => Self::Or(pat.into()),
+            crate::Pat::Path(pat) => Self::Path(text(pat)),
+            crate::Pat::Reference(pat) => Self::Reference(pat.into()),
+            crate::Pat::Rest(pat) => Self::Rest(pat.into()),
+            crate::Pat::Struct(pat) => Self::Struct(pat.into()),
+            crate::Pat::Tuple(pat) => Self::Tuple(pat.into()),
+            crate::Pat::TupleStruct(pat) => Self::TupleStruct(pat.into()),
+            crate::Pat::Type(pat) => Self::Type(pat.into()),
+            crate::Pat::Verbatim(pat) => Self::Verbatim(pat.to_string()),
+            crate::Pat::Wild(pat) => Self::Wild(pat.into()),
+        }
+    }
+}
+
+impl Repr for Pat {
+    type Node = crate::Pat;
+
+    fn into_node(self) -> Result<Self::Node> {
+        Ok(match self {
+            Self::Ident(pat) => crate::Pat::Ident(pat.into_node()?),
+            Self::Lit(pat) => crate::Pat::Lit(parse(&pat)?),
+            Self::Or(pat) => crate::Pat::Or(pat.into_node()?),
+            Self::Path(pat) => crate::Pat::Path(parse(&pat)?),
+            Self::Reference(pat) => crate::Pat::Reference(pat.into_node()?),
+            Self::Rest(pat) => crate::Pat::Rest(pat.into_node()?),
+            Self::Struct(pat) => crate::Pat::Struct(pat.into_node()?),
+            Self::Tuple(pat) => crate::Pat::Tuple(pat.into_node()?),
+            Self::TupleStruct(pat) => crate::Pat::TupleStruct(pat.into_node()?),
+            Self::Type(pat) => crate::Pat::Type(pat.into_node()?),
+            Self::Verbatim(pat) => crate::Pat::Verbatim(parse(&pat)?),
+            Self::Wild(pat) => crate::Pat::Wild(pat.into_node()?),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct PatIdent {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    attrs: Vec<String>,
+    #[serde(rename = "ref", default, skip_serializing_if = "not")]
+    by_ref: bool,
+    #[serde(rename = "mut", default, skip_serializing_if = "not")]
+    mutability: bool,
+    ident: String,
+}
+
+impl From<&crate::PatIdent> for PatIdent {
+    fn from(node: &crate::PatIdent) -> Self {
+        Self {
+            attrs: attrs_text(&node.attrs),
+            by_ref: node.by_ref.is_some(),
+            mutability: node.mutability.is_some(),
+            ident: node.ident.to_string(),
+        }
+    }
+}
+
+impl Repr for PatIdent {
+    type Node = crate::PatIdent;
+
+    fn into_node(self) -> Result<Self::Node> {
+        let span = Span::call_site();
+        Ok(crate::PatIdent {
+            attrs: parse_attrs(&self.attrs)?,
+            by_ref: self.by_ref.then(|| Token![ref](span)),
+            mutability: self.mutability.then(|| Token![mut](span)),
+            ident: parse_ident(&self.ident)?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct PatOr {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    attrs: Vec<String>,
+    #[serde(default, skip_serializing_if = "not")]
+    leading_vert: bool,
+    cases: Vec<Pat>,
+}
+
+impl From<&crate::PatOr> for PatOr {
+    fn from(node: &crate::PatOr) -> Self {
+        Self {
+            attrs: attrs_text(&node.attrs),
+            leading_vert: node.leading_vert.is_some(),
+            cases: node.cases.iter().map(Pat::from).collect(),
+        }
+    }
+}
+
+impl Repr for PatOr {
+    type Node = crate::PatOr;
+
+    fn into_node(self) -> Result<Self::Node> {
+        let span = Span::call_site();
+        Ok(crate::PatOr {
+            attrs: parse_attrs(&self.attrs)?,
+            leading_vert: self.leading_vert.then(|| Token![|](span)),
+            cases: parse_punctuated(self.cases, false)?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct PatReference {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    attrs: Vec<String>,
+    #[serde(rename = "mut", default, skip_serializing_if = "not")]
+    mutability: bool,
+    pat: Box<Pat>,
+}
+
+impl From<&crate::PatReference> for PatReference {
+    fn from(node: &crate::PatReference) -> Self {
+        Self {
+            attrs: attrs_text(&node.attrs),
+            mutability: node.mutability.is_some(),
+            pat: Box::new((&*node.pat).into()),
+        }
+    }
+}
+
+impl Repr for PatReference {
+    type Node = crate::PatReference;
+
+    fn into_node(self) -> Result<Self::Node> {
+        let span = Span::call_site();
+        Ok(crate::PatReference {
+            attrs: parse_attrs(&self.attrs)?,
+            and_token: Token![&](span),
+            mutability: self.mutability.then(|| Token![mut](span)),
+            pat: into_boxed_node(*self.pat)?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct PatRest {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    attrs: Vec<String>,
+}
+
+impl From<&crate::PatRest> for PatRest {
+    fn from(node: &crate::PatRest) -> Self {
+        Self { attrs: attrs_text(&node.attrs) }
+    }
+}
+
+impl Repr for PatRest {
+    type Node = crate::PatRest;
+
+    fn into_node(self) -> Result<Self::Node> {
+        Ok(crate::PatRest {
+            attrs: parse_attrs(&self.attrs)?,
+            dot2_token: Token![..](Span::call_site()),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct PatStruct {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    attrs: Vec<String>,
+    path: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    fields: Vec<FieldPat>,
+    #[serde(default, skip_serializing_if = "not")]
+    trailing_comma: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rest: Option<PatRest>,
+}
+
+impl From<&crate::PatStruct> for PatStruct {
+    fn from(node: &crate::PatStruct) -> Self {
+        Self {
+            attrs: attrs_text(&node.attrs),
+            path: text(&node.path),
+            fields: node.fields.iter().map(FieldPat::from).collect(),
+            trailing_comma: node.fields.trailing_punct(),
+            rest: node.rest.as_ref().map(PatRest::from),
+        }
+    }
+}
+
+impl Repr for PatStruct {
+    type Node = crate::PatStruct;
+
+    fn into_node(self) -> Result<Self::Node> {
+        let span = Span::call_site();
+        Ok(crate::PatStruct {
+            attrs: parse_attrs(&self.attrs)?,
+            path: parse(&self.path)?,
+            brace_token: token::Brace(span),
+            fields: parse_punctuated(self.fields, self.trailing_comma)?,
+            rest: self.rest.map(PatRest::into_node).transpose()?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct PatTuple {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    attrs: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    elems: Vec<Pat>,
+    #[serde(default, skip_serializing_if = "not")]
+    trailing_comma: bool,
+}
+
+impl From<&crate::PatTuple> for PatTuple {
+    fn from(node: &crate::PatTuple) -> Self {
+        Self {
+            attrs: attrs_text(&node.attrs),
+            elems: node.elems.iter().map(Pat::from).collect(),
+            trailing_comma: node.elems.trailing_punct(),
+        }
+    }
+}
+
+impl Repr for PatTuple {
+    type Node = crate::PatTuple;
+
+    fn into_node(self) -> Result<Self::Node> {
+        let span = Span::call_site();
+        Ok(crate::PatTuple {
+            attrs: parse_attrs(&self.attrs)?,
+            paren_token: token::Paren(span),
+            elems: parse_punctuated(self.elems, self.trailing_comma)?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct PatTupleStruct {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    attrs: Vec<String>,
+    path: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    elems: Vec<Pat>,
+    #[serde(default, skip_serializing_if = "not")]
+    trailing_comma: bool,
+}
+
+impl From<&crate::PatTupleStruct> for PatTupleStruct {
+    fn from(node: &crate::PatTupleStruct) -> Self {
+        Self {
+            attrs: attrs_text(&node.attrs),
+            path: text(&node.path),
+            elems: node.elems.iter().map(Pat::from).collect(),
+            trailing_comma: node.elems.trailing_punct(),
+        }
+    }
+}
+
+impl Repr for PatTupleStruct {
+    type Node = crate::PatTupleStruct;
+
+    fn into_node(self) -> Result<Self::Node> {
+        let span = Span::call_site();
+        Ok(crate::PatTupleStruct {
+            attrs: parse_attrs(&self.attrs)?,
+            path: parse(&self.path)?,
+            paren_token: token::Paren(span),
+            elems: parse_punctuated(self.elems, self.trailing_comma)?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct PatType {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    attrs: Vec<String>,
+    pat: Box<Pat>,
+    ty: String,
+}
+
+impl From<&crate::PatType> for PatType {
+    fn from(node: &crate::PatType) -> Self {
+        Self {
+            attrs: attrs_text(&node.attrs),
+            pat: Box::new((&*node.pat).into()),
+            ty: text(&node.ty),
+        }
+    }
+}
+
+impl Repr for PatType {
+    type Node = crate::PatType;
+
+    fn into_node(self) -> Result<Self::Node> {
+        let span = Span::call_site();
+        Ok(crate::PatType {
+            attrs: parse_attrs(&self.attrs)?,
+            pat: into_boxed_node(*self.pat)?,
+            colon_token: Token![:](span),
+            ty: Box::new(parse(&self.ty)?),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct PatWild {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    attrs: Vec<String>,
+}
+
+impl From<&crate::PatWild> for PatWild {
+    fn from(node: &crate::PatWild) -> Self {
+        Self { attrs: attrs_text(&node.attrs) }
+    }
+}
+
+impl Repr for PatWild {
+    type Node = crate::PatWild;
+
+    fn into_node(self) -> Result<Self::Node> {
+        Ok(crate::PatWild {
+            attrs: parse_attrs(&self.attrs)?,
+            underscore_token: Token![_](Span::call_site()),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct FieldPat {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    attrs: Vec<String>,
+    member: String,
+    #[serde(default, skip_serializing_if = "not")]
+    colon_token: bool,
+    pat: Box<Pat>,
+}
+
+impl From<&crate::FieldPat> for FieldPat {
+    fn from(node: &crate::FieldPat) -> Self {
+        Self {
+            attrs: attrs_text(&node.attrs),
+            member: text(&node.member),
+            colon_token: node.colon_token.is_some(),
+            pat: Box::new((&*node.pat).into()),
+        }
+    }
+}
+
+impl Repr for FieldPat {
+    type Node = crate::FieldPat;
+
+    fn into_node(self) -> Result<Self::Node> {
+        let span = Span::call_site();
+        Ok(crate::FieldPat {
+            attrs: parse_attrs(&self.attrs)?,
+            member: parse(&self.member)?,
+            colon_token: self.colon_token.then(|| Token![:](span)),
+            pat: into_boxed_node(*self.pat)?,
+        })
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Expression
+
+#[derive(Serialize, Deserialize)]
+struct ExprMatch {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    attrs: Vec<String>,
+    expr: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    arms: Vec<Arm>,
+}
+
+impl From<&crate::ExprMatch> for ExprMatch {
+    fn from(node: &crate::ExprMatch) -> Self {
+        Self {
+            attrs: attrs_text(&node.attrs),
+            expr: node.expr.to_string(),
+            arms: node.arms.iter().map(Arm::from).collect(),
+        }
+    }
+}
+
+impl Repr for ExprMatch {
+    type Node = crate::ExprMatch;
+
+    fn into_node(self) -> Result<Self::Node> {
+        let span = Span::call_site();
+        Ok(crate::ExprMatch {
+            attrs: parse_attrs(&self.attrs)?,
+            match_token: Token![match](span),
+            expr: parse(&self.expr)?,
+            brace_token: token::Brace(span),
+            arms: self.arms.into_iter().map(Arm::into_node).collect::<Result<_>>()?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Arm {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    attrs: Vec<String>,
+    pat: Pat,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    guard: Option<String>,
+    body: String,
+    #[serde(default, skip_serializing_if = "not")]
+    comma: bool,
+}
+
+impl From<&crate::Arm> for Arm {
+    fn from(node: &crate::Arm) -> Self {
+        Self {
+            attrs: attrs_text(&node.attrs),
+            pat: (&node.pat).into(),
+            guard: node.guard.as_ref().map(|(_, guard)| guard.to_string()),
+            body: node.body.to_string(),
+            comma: node.comma.is_some(),
+        }
+    }
+}
+
+impl Repr for Arm {
+    type Node = crate::Arm;
+
+    fn into_node(self) -> Result<Self::Node> {
+        let span = Span::call_site();
+        Ok(crate::Arm {
+            attrs: parse_attrs(&self.attrs)?,
+            pat: self.pat.into_node()?,
+            guard: match self.guard {
+                Some(guard) => Some((Token![if](span), parse(&guard)?)),
+                None => None,
+            },
+            fat_arrow_token: Token![=>](span),
+            body: parse(&self.body)?,
+            comma: self.comma.then(|| Token![,](span)),
+        })
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Statement
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Stmt {
+    Local(Local),
+    Item(String),
+    Expr {
+        expr: String,
+        #[serde(default, skip_serializing_if = "not")]
+        semi: bool,
+    },
+    Macro(StmtMacro),
+}
+
+impl From<&crate::Stmt> for Stmt {
+    fn from(node: &crate::Stmt) -> Self {
+        match node {
+            crate::Stmt::Local(local) => Self::Local(local.into()),
+            crate::Stmt::Item(item) => Self::Item(item.to_string()),
+            crate::Stmt::Expr(expr, semi) => {
+                Self::Expr { expr: expr.to_string(), semi: semi.is_some() }
+            }
+            crate::Stmt::Macro(mac) => Self::Macro(mac.into()),
+        }
+    }
+}
+
+impl Repr for Stmt {
+    type Node = crate::Stmt;
+
+    fn into_node(self) -> Result<Self::Node> {
+        let span = Span::call_site();
+        Ok(match self {
+            Self::Local(local) => crate::Stmt::Local(local.into_node()?),
+            Self::Item(item) => crate::Stmt::Item(parse(&item)?),
+            Self::Expr { expr, semi } => {
+                crate::Stmt::Expr(parse(&expr)?, semi.then(|| Token![;](span)))
+            }
+            Self::Macro(mac) => crate::Stmt::Macro(mac.into_node()?),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Local {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    attrs: Vec<String>,
+    pat: Pat,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    init: Option<LocalInit>,
+}
+
+impl From<&crate::Local> for Local {
+    fn from(node: &crate::Local) -> Self {
+        Self {
+            attrs: attrs_text(&node.attrs),
+            pat: (&node.pat).into(),
+            init: node.init.as_ref().map(LocalInit::from),
+        }
+    }
+}
+
+impl Repr for Local {
+    type Node = crate::Local;
+
+    fn into_node(self) -> Result<Self::Node> {
+        let span = Span::call_site();
+        Ok(crate::Local {
+            attrs: parse_attrs(&self.attrs)?,
+            let_token: Token![let](span),
+            pat: self.pat.into_node()?,
+            init: self.init.map(LocalInit::into_node).transpose()?,
+            semi_token: Token![;](span),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct LocalInit {
+    expr: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    diverge: Option<Block>,
+}
+
+impl From<&crate::LocalInit> for LocalInit {
+    fn from(node: &crate::LocalInit) -> Self {
+        Self {
+            expr: node.expr.to_string(),
+            diverge: node.diverge.as_ref().map(|(_, block)| block.into()),
+        }
+    }
+}
+
+impl Repr for LocalInit {
+    type Node = crate::LocalInit;
+
+    fn into_node(self) -> Result<Self::Node> {
+        let span = Span::call_site();
+        Ok(crate::LocalInit {
+            eq_token: Token![=](span),
+            expr: parse(&self.expr)?,
+            diverge: match self.diverge {
+                Some(block) => Some((Token![else](span), block.into_node()?)),
+                None => None,
+            },
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct StmtMacro {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    attrs: Vec<String>,
+    mac: String,
+    #[serde(default, skip_serializing_if = "not")]
+    semi: bool,
+}
+
+impl From<&crate::StmtMacro> for StmtMacro {
+    fn from(node: &crate::StmtMacro) -> Self {
+        Self {
+            attrs: attrs_text(&node.attrs),
+            mac: text(&node.mac),
+            semi: node.semi_token.is_some(),
+        }
+    }
+}
+
+impl Repr for StmtMacro {
+    type Node = crate::StmtMacro;
+
+    fn into_node(self) -> Result<Self::Node> {
+        let span = Span::call_site();
+        Ok(crate::StmtMacro {
+            attrs: parse_attrs(&self.attrs)?,
+            mac: parse(&self.mac)?,
+            semi_token: self.semi.then(|| Token![;](span)),
+        })
+    }
+}
@@ -0,0 +1,726 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Based on https://github.com/dtolnay/syn/blob/2.0.37/src/stmt.rs.
+
+use alloc::vec::Vec;
+#[cfg(feature = "extra-traits")]
+use core::hash::{Hash, Hasher};
+
+use proc_macro2::TokenStream;
+use syn::{Attribute, Macro, Token};
+
+use super::{Block, Pat};
+#[cfg(feature = "extra-traits")]
+use crate::tt::TokenStreamHelper;
+
+ast_enum! {
+    /// A statement, usually ending in a semicolon.
+    ///
+    /// Unlike syn's `Stmt`, expressions and items are not parsed and are kept
+    /// as tokens. The trailing expression of a block, if any, is the last
+    /// statement and is represented as `Stmt::Expr(tokens, None)`.
+    pub enum Stmt #manual_extra_traits {
+        /// A local (let) binding.
+        Local(Local),
+
+        /// An item definition, including its outer attributes.
+        Item(TokenStream),
+
+        /// Expression, with or without trailing semicolon.
+        ///
+        /// The tokens include the outer attributes of the expression.
+        Expr(TokenStream, Option<Token![;]>),
+
+        /// A macro invocation in statement position.
+        ///
+        /// Syntactically it's ambiguous which other kind of statement this
+        /// macro would expand to. It can be any of local variable (`let`),
+        /// item, or expression.
+        Macro(StmtMacro),
+    }
+}
+
+ast_struct! {
+    /// A local `let` binding: `let x: u64 = s.parse()?;`.
+    pub struct Local {
+        pub attrs: Vec<Attribute>,
+        pub let_token: Token![let],
+        /// The pattern of the binding. Patterns that this crate does not
+        /// support, such as `[a, b]`, are kept as [`Pat::Verbatim`].
+        pub pat: Pat,
+        pub init: Option<LocalInit>,
+        pub semi_token: Token![;],
+    }
+}
+
+ast_struct! {
+    /// The expression assigned in a local `let` binding, including optional
+    /// diverging `else` block.
+    ///
+    /// `LocalInit` represents `= s.parse()?` in `let x: u64 = s.parse()?` and
+    /// `= r else { return }` in `let Ok(x) = r else { return }`.
+    pub struct LocalInit #manual_extra_traits {
+        pub eq_token: Token![=],
+        /// The initializer expression, not parsed.
+        pub expr: TokenStream,
+        pub diverge: Option<(Token![else], Block)>,
+    }
+}
+
+ast_struct! {
+    /// A macro invocation in statement position.
+    ///
+    /// Syntactically it's ambiguous which other kind of statement this macro
+    /// would expand to. It can be any of local variable (`let`), item, or
+    /// expression.
+    pub struct StmtMacro {
+        pub attrs: Vec<Attribute>,
+        pub mac: Macro,
+        pub semi_token: Option<Token![;]>,
+    }
+}
+
+#[cfg(feature = "extra-traits")]
+impl Eq for Stmt {}
+
+#[cfg(feature = "extra-traits")]
+impl PartialEq for Stmt {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Local(this), Self::Local(other)) => this == other,
+            (Self::Item(this), Self::Item(other)) => {
+                TokenStreamHelper(this) == TokenStreamHelper(other)
+            }
+            (Self::Expr(this, this_semi), Self::Expr(other, other_semi)) => {
+                TokenStreamHelper(this) == TokenStreamHelper(other) && this_semi == other_semi
+            }
+            (Self::Macro(this), Self::Macro(other)) => this == other,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(feature = "extra-traits")]
+impl Hash for Stmt {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Self::Local(local) => {
+                state.write_u8(0);
+                local.hash(state);
+            }
+            Self::Item(item) => {
+                state.write_u8(1);
+                TokenStreamHelper(item).hash(state);
+            }
+            Self::Expr(expr, semi) => {
+                state.write_u8(2);
+                TokenStreamHelper(expr).hash(state);
+                semi.hash(state);
+            }
+            Self::Macro(mac) => {
+                state.write_u8(3);
+                mac.hash(state);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "extra-traits")]
+impl Eq for LocalInit {}
+
+#[cfg(feature = "extra-traits")]
+impl PartialEq for LocalInit {
+    fn eq(&self, other: &Self) -> bool {
+        TokenStreamHelper(&self.expr) == TokenStreamHelper(&other.expr)
+            && self.diverge == other.diverge
+    }
+}
+
+#[cfg(feature = "extra-traits")]
+impl Hash for LocalInit {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        TokenStreamHelper(&self.expr).hash(state);
+        self.diverge.hash(state);
+    }
+}
+
+#[cfg(feature = "parsing")]
+pub(crate) mod parsing {
+    use alloc::{boxed::Box, vec::Vec};
+
+    use proc_macro2::{Delimiter, Spacing, TokenStream, TokenTree};
+    use syn::{
+        Attribute, Ident, Lifetime, LitStr, Path, Token, Type, Visibility,
+        parse::{Parse, ParseStream, Parser as _, Result, discouraged::Speculative as _},
+        token,
+    };
+
+    use super::{Local, LocalInit, Stmt, StmtMacro};
+    use crate::{
+        Block,
+        pat::{Pat, PatType, parsing::pat_or_verbatim},
+        verbatim,
+    };
+
+    #[derive(Clone, Copy)]
+    struct AllowNoSemi(bool);
+
+    impl Block {
+        /// Parse the body of a block as zero or more statements, possibly
+        /// including one trailing expression.
+        ///
+        /// Expressions and items are split off at their top-level boundaries
+        /// without being parsed.
+        pub fn parse_within(input: ParseStream<'_>) -> Result<Vec<Stmt>> {
+            let mut stmts = Vec::new();
+            loop {
+                while let semi @ Some(_) = input.parse()? {
+                    stmts.push(Stmt::Expr(TokenStream::new(), semi));
+                }
+                if input.is_empty() {
+                    break;
+                }
+                let stmt = parse_stmt(input, AllowNoSemi(true))?;
+                stmts.push(stmt);
+            }
+            Ok(stmts)
+        }
+
+        /// Parses the body of a block like
+        /// [`parse_within`](Self::parse_within), and also returns the tokens
+        /// of each statement as they appear in the input.
+        #[cfg(feature = "printing")]
+        pub(crate) fn parse_within_verbatim(
+            input: ParseStream<'_>,
+        ) -> Result<Vec<(Stmt, TokenStream)>> {
+            let mut stmts = Vec::new();
+            while !input.is_empty() {
+                let begin = input.fork();
+                let stmt = match input.parse()? {
+                    semi @ Some(_) => Stmt::Expr(TokenStream::new(), semi),
+                    None => parse_stmt(input, AllowNoSemi(true))?,
+                };
+                stmts.push((stmt, verbatim::between(&begin, input)));
+            }
+            Ok(stmts)
+        }
+
+        /// Splits [`stmts`](Self::stmts) into top-level statements.
+        ///
+        /// This is equivalent to parsing `stmts` with
+        /// [`Block::parse_within`].
+        pub fn split_stmts(&self) -> Result<Vec<Stmt>> {
+            Self::parse_within.parse2(self.stmts.clone())
+        }
+    }
+
+    impl Parse for Stmt {
+        fn parse(input: ParseStream<'_>) -> Result<Self> {
+            let allow_nosemi = AllowNoSemi(false);
+            parse_stmt(input, allow_nosemi)
+        }
+    }
+
+    fn parse_stmt(input: ParseStream<'_>, allow_nosemi: AllowNoSemi) -> Result<Stmt> {
+        let begin = input.fork();
+        let attrs = input.call(Attribute::parse_outer)?;
+
+        // brace-style macros; paren and bracket macros get parsed as
+        // expression statements.
+        let ahead = input.fork();
+        let mut is_item_macro = false;
+        if ahead.call(Path::parse_mod_style).is_ok() && ahead.peek(Token![!]) {
+            if ahead.peek2(Ident) || ahead.peek2(Token![try]) {
+                is_item_macro = true;
+            } else if ahead.peek2(token::Brace)
+                && !(ahead.peek3(Token![.]) && !ahead.peek3(Token![..]) || ahead.peek3(Token![?]))
+            {
+                return stmt_mac(input, attrs).map(Stmt::Macro);
+            }
+        }
+
+        if input.peek(Token![let]) && !input.peek(token::Group) {
+            stmt_local(input, attrs).map(Stmt::Local)
+        } else if peek_item(input) || is_item_macro {
+            let mut tokens = verbatim::between(&begin, input);
+            stmt_item(input, &mut tokens)?;
+            Ok(Stmt::Item(tokens))
+        } else {
+            stmt_expr(input, allow_nosemi, &begin, attrs)
+        }
+    }
+
+    /// Returns `true` if the input starts with an item other than a macro
+    /// invocation, after any outer attributes.
+    pub(crate) fn peek_item(input: ParseStream<'_>) -> bool {
+        if peek_fn_pointer(input) {
+            return false;
+        }
+        input.peek(Token![pub])
+            || input.peek(Token![crate]) && !input.peek2(Token![::])
+            || input.peek(Token![extern])
+            || input.peek(Token![use])
+            || input.peek(Token![static])
+                && (input.peek2(Token![mut])
+                    || input.peek2(Ident)
+                        && !(input.peek2(Token![async])
+                            && (input.peek3(Token![move]) || input.peek3(Token![|]))))
+            || input.peek(Token![const])
+                && !(input.peek2(token::Brace)
+                    || input.peek2(Token![static])
+                    || input.peek2(Token![async])
+                        && !(input.peek3(Token![unsafe])
+                            || input.peek3(Token![extern])
+                            || input.peek3(Token![fn]))
+                    || input.peek2(Token![move])
+                    || input.peek2(Token![|]))
+            || input.peek(Token![unsafe]) && !input.peek2(token::Brace)
+            || input.peek(Token![async])
+                && (input.peek2(Token![unsafe])
+                    || input.peek2(Token![extern])
+                    || input.peek2(Token![fn]))
+            || input.peek(Token![fn])
+            || input.peek(Token![mod])
+            || input.peek(Token![type])
+            || input.peek(Token![struct])
+            || input.peek(Token![enum])
+            || input.peek(Token![union]) && input.peek2(Ident)
+            || input.peek(Token![auto]) && input.peek2(Token![trait])
+            || input.peek(Token![trait])
+            || input.peek(Token![default])
+                && (input.peek2(Token![unsafe]) || input.peek2(Token![impl]))
+            || input.peek(Token![impl])
+            || input.peek(Token![macro])
+    }
+
+    /// Returns `true` if the input starts with a function pointer type:
+    /// `unsafe extern "C" fn(u8)`.
+    fn peek_fn_pointer(input: ParseStream<'_>) -> bool {
+        let ahead = input.fork();
+        let _: Result<Option<Token![unsafe]>> = ahead.parse();
+        if let Ok(Some(_)) = ahead.parse::<Option<Token![extern]>>() {
+            let _: Result<Option<LitStr>> = ahead.parse();
+        }
+        ahead.peek(Token![fn]) && ahead.peek2(token::Paren)
+    }
+
+    fn stmt_mac(input: ParseStream<'_>, attrs: Vec<Attribute>) -> Result<StmtMacro> {
+        let mac: syn::Macro = input.parse()?;
+        let semi_token: Option<Token![;]> = input.parse()?;
+
+        Ok(StmtMacro { attrs, mac, semi_token })
+    }
+
+    fn stmt_local(input: ParseStream<'_>, attrs: Vec<Attribute>) -> Result<Local> {
+        let let_token: Token![let] = input.parse()?;
+
+        let mut pat = pat_or_verbatim(input, Pat::parse_single, |input| {
+            input.peek(Token![:]) && !input.peek(Token![::])
+                || input.peek(Token![=])
+                || input.peek(Token![;])
+        })?;
+        if input.peek(Token![:]) {
+            let colon_token: Token![:] = input.parse()?;
+            let ty: Type = input.parse()?;
+            pat = Pat::Type(PatType {
+                attrs: Vec::new(),
+                pat: Box::new(pat),
+                colon_token,
+                ty: Box::new(ty),
+            });
+        }
+
+        let init = if let Some(eq_token) = input.parse()? {
+            let eq_token: Token![=] = eq_token;
+            let mut expr = TokenStream::new();
+            let mut trailing_brace = false;
+            while !input.is_empty()
+                && !input.peek(Token![;])
+                && !(input.peek(Token![else]) && !trailing_brace)
+            {
+                let tt: TokenTree = input.parse()?;
+                trailing_brace = is_brace(&tt);
+                expr.extend([tt]);
+            }
+
+            let diverge = if input.peek(Token![else]) {
+                let else_token: Token![else] = input.parse()?;
+                let diverge: Block = input.parse()?;
+                Some((else_token, diverge))
+            } else {
+                None
+            };
+
+            Some(LocalInit { eq_token, expr, diverge })
+        } else {
+            None
+        };
+
+        let semi_token: Token![;] = input.parse()?;
+
+        Ok(Local { attrs, let_token, pat, init, semi_token })
+    }
+
+    pub(crate) fn stmt_item(input: ParseStream<'_>, tokens: &mut TokenStream) -> Result<()> {
+        let ahead = input.fork();
+        let _: Visibility = ahead.parse()?;
+        let semi_terminated = ahead.peek(Token![use])
+            || ahead.peek(Token![type])
+            || ahead.peek(Token![static])
+            || ahead.peek(Token![extern]) && ahead.peek2(Token![crate])
+            || ahead.peek(Token![const])
+                && !(ahead.peek2(Token![fn])
+                    || ahead.peek2(Token![unsafe])
+                    || ahead.peek2(Token![async])
+                    || ahead.peek2(Token![extern]));
+
+        // The initializers of semicolon-terminated items are expressions, in
+        // which `<` can be a comparison or a shift, so they end at the first
+        // `;` outside of groups.
+        if semi_terminated {
+            loop {
+                let tt: TokenTree = input.parse()?;
+                let done = matches!(&tt, TokenTree::Punct(p) if p.as_char() == ';');
+                tokens.extend([tt]);
+                if done {
+                    return Ok(());
+                }
+            }
+        }
+
+        // Braces in generic arguments (`Foo<{ N }>`) are not the body of the
+        // item, so track the depth of angle brackets. Outside of groups, the
+        // other items only have generics and types before their body.
+        let mut angle_depth = 0_usize;
+        let mut prev_joint = None;
+        loop {
+            let tt: TokenTree = input.parse()?;
+            let done = match &tt {
+                TokenTree::Punct(p) if p.as_char() == ';' => angle_depth == 0,
+                TokenTree::Punct(p) if p.as_char() == '<' => {
+                    angle_depth += 1;
+                    false
+                }
+                TokenTree::Punct(p) if p.as_char() == '>' => {
+                    // `->` and `=>`
+                    if !matches!(prev_joint, Some('-' | '=')) {
+                        angle_depth = angle_depth.saturating_sub(1);
+                    }
+                    false
+                }
+                TokenTree::Group(g) => angle_depth == 0 && g.delimiter() == Delimiter::Brace,
+                _ => false,
+            };
+            prev_joint = match &tt {
+                TokenTree::Punct(p) if p.spacing() == Spacing::Joint => Some(p.as_char()),
+                _ => None,
+            };
+            tokens.extend([tt]);
+            if done {
+                return Ok(());
+            }
+        }
+    }
+
+    /// `begin` is a fork of `input` from before the outer attributes `attrs`.
+    fn stmt_expr(
+        input: ParseStream<'_>,
+        allow_nosemi: AllowNoSemi,
+        begin: ParseStream<'_>,
+        attrs: Vec<Attribute>,
+    ) -> Result<Stmt> {
+        // Paren and bracket macros followed by a semicolon.
+        let ahead = input.fork();
+        if let Ok(mac) = ahead.parse::<syn::Macro>() {
+            if ahead.peek(Token![;]) {
+                let semi_token = Some(ahead.parse()?);
+                input.advance_to(&ahead);
+                return Ok(Stmt::Macro(StmtMacro { attrs, mac, semi_token }));
+            }
+        }
+
+        let mut e = verbatim::between(begin, input);
+
+        if is_block_like(input)? {
+            block_like(input, &mut e)?;
+            if !(input.peek(Token![.]) && !input.peek(Token![..]) || input.peek(Token![?])) {
+                let semi_token: Option<Token![;]> = input.parse()?;
+                return Ok(Stmt::Expr(e, semi_token));
+            }
+        }
+
+        while !input.is_empty() && !input.peek(Token![;]) {
+            e.extend([input.parse::<TokenTree>()?]);
+        }
+
+        let semi_token: Option<Token![;]> = input.parse()?;
+        if semi_token.is_some() || allow_nosemi.0 {
+            Ok(Stmt::Expr(e, semi_token))
+        } else {
+            Err(input.error("expected semicolon"))
+        }
+    }
+
+    /// Returns `true` if the input starts with an expression that can be used
+    /// as a statement without a trailing semicolon: `if`, `match`, `loop`,
+    /// `while`, `for`, and (optionally labeled, `unsafe` or `const`) blocks.
+    pub(crate) fn is_block_like(input: ParseStream<'_>) -> Result<bool> {
+        let ahead = input.fork();
+        ahead.call(Attribute::parse_outer)?;
+        if ahead.peek(Lifetime) && ahead.peek2(Token![:]) {
+            let _: Lifetime = ahead.parse()?;
+            let _: Token![:] = ahead.parse()?;
+        }
+        Ok(ahead.peek(Token![if])
+            || ahead.peek(Token![match])
+            || ahead.peek(Token![loop])
+            || ahead.peek(Token![while])
+            || ahead.peek(Token![for])
+            || ahead.peek(token::Brace)
+            || (ahead.peek(Token![unsafe]) || ahead.peek(Token![const]) || ahead.peek(Token![try]))
+                && ahead.peek2(token::Brace))
+    }
+
+    /// Consumes a block-like expression (see [`is_block_like`]) and appends
+    /// its tokens to `tokens`.
+    pub(crate) fn block_like(input: ParseStream<'_>, tokens: &mut TokenStream) -> Result<()> {
+        let begin = input.fork();
+        input.call(Attribute::parse_outer)?;
+        if input.peek(Lifetime) && input.peek2(Token![:]) {
+            let _: Lifetime = input.parse()?;
+            let _: Token![:] = input.parse()?;
+        }
+        tokens.extend(verbatim::between(&begin, input));
+        if input.peek(Token![if]) {
+            tokens.extend([input.parse::<TokenTree>()?]);
+            until_brace(input, tokens, false)?;
+            brace(input, tokens)?;
+            if input.peek(Token![else]) {
+                tokens.extend([input.parse::<TokenTree>()?]);
+                if input.peek(Token![if]) {
+                    block_like(input, tokens)?;
+                } else {
+                    brace(input, tokens)?;
+                }
+            }
+        } else if input.peek(Token![match]) || input.peek(Token![while]) || input.peek(Token![for])
+        {
+            let in_pat = input.peek(Token![for]);
+            tokens.extend([input.parse::<TokenTree>()?]);
+            until_brace(input, tokens, in_pat)?;
+            brace(input, tokens)?;
+        } else if input.peek(Token![loop])
+            || input.peek(Token![unsafe])
+            || input.peek(Token![const])
+            || input.peek(Token![try])
+        {
+            tokens.extend([input.parse::<TokenTree>()?]);
+            brace(input, tokens)?;
+        } else {
+            brace(input, tokens)?;
+        }
+        Ok(())
+    }
+
+    /// Consumes the tokens of a condition or scrutinee, up to the brace that
+    /// starts the body.
+    ///
+    /// Braces in the patterns of `if let`, `while let`, and `for` are part of
+    /// the pattern, so `in_pat` tracks whether we are in one.
+    pub(crate) fn until_brace(
+        input: ParseStream<'_>,
+        tokens: &mut TokenStream,
+        mut in_pat: bool,
+    ) -> Result<()> {
+        let mut prev_joint = false;
+        while !(input.peek(token::Brace) && !in_pat) {
+            if input.is_empty() {
+                return Err(input.error("expected `{`"));
+            }
+            if input.peek(Token![let]) {
+                in_pat = true;
+            } else if input.peek(Token![in])
+                || input.peek(Token![=])
+                    && !prev_joint
+                    && !input.peek(Token![==])
+                    && !input.peek(Token![=>])
+            {
+                in_pat = false;
+            }
+            if !in_pat && is_block_like(input)? {
+                block_like(input, tokens)?;
+                prev_joint = false;
+            } else {
+                let tt: TokenTree = input.parse()?;
+                prev_joint = matches!(&tt, TokenTree::Punct(p) if p.spacing() == Spacing::Joint);
+                tokens.extend([tt]);
+            }
+        }
+        Ok(())
+    }
+
+    /// Consumes the tokens of an expression up to the next top-level `,` or
+    /// `;`, or to the end of input.
+    pub(crate) fn expr_tokens(input: ParseStream<'_>, tokens: &mut TokenStream) -> Result<()> {
+        // Commas in generic arguments (`f::<A, B>()`, `<A as B<C, D>>::f()`,
+        // `a as Vec<B, C>`) do not end the expression, so track the depth of
+        // angle brackets opened after `::`, where an operand is expected, and
+        // after a path in type position.
+        let mut angle_depth = 0_usize;
+        let mut prev: [Option<(char, Spacing)>; 2] = [None; 2];
+        // Whether the last token ends an operand, so that `<` and `|` after it
+        // are binary operators.
+        let mut operand_end = false;
+        // Whether the tokens since the last `as` or `->` are a type.
+        let mut type_pos = false;
+        let mut after_ident = false;
+        while !input.is_empty()
+            && !(angle_depth == 0 && (input.peek(Token![,]) || input.peek(Token![;])))
+        {
+            // Commas in the parameters of a closure (`|a, b| a + b`) do not end
+            // the expression either.
+            if angle_depth == 0
+                && !operand_end
+                && !matches!(prev[1], Some((_, Spacing::Joint)))
+                && (input.peek(Token![|]) || input.peek(Token![||]))
+            {
+                if input.peek(Token![||]) {
+                    tokens.extend([input.parse::<TokenTree>()?, input.parse::<TokenTree>()?]);
+                } else {
+                    tokens.extend([input.parse::<TokenTree>()?]);
+                    while !input.peek(Token![|]) {
+                        tokens.extend([input.parse::<TokenTree>()?]);
+                    }
+                    tokens.extend([input.parse::<TokenTree>()?]);
+                }
+                prev = [None, None];
+                after_ident = false;
+                if input.peek(Token![->]) {
+                    tokens.extend([input.parse::<TokenTree>()?, input.parse::<TokenTree>()?]);
+                    type_pos = true;
+                }
+                continue;
+            }
+
+            let tt: TokenTree = input.parse()?;
+            let punct = match &tt {
+                TokenTree::Punct(p) => Some((p.as_char(), p.spacing())),
+                _ => None,
+            };
+            match punct {
+                Some(('<', _))
+                    if angle_depth > 0
+                        || !operand_end && !matches!(prev[1], Some((_, Spacing::Joint)))
+                        || type_pos && after_ident
+                        || matches!(prev, [Some((':', Spacing::Joint)), Some((':', _))]) =>
+                {
+                    angle_depth += 1;
+                    operand_end = false;
+                }
+                Some(('>', _))
+                    if !matches!(prev[1], Some(('-' | '=', Spacing::Joint))) && angle_depth > 0 =>
+                {
+                    angle_depth -= 1;
+                    operand_end = true;
+                }
+                Some(('?', _)) => operand_end = true,
+                Some(_) => operand_end = false,
+                None => {
+                    operand_end = match &tt {
+                        TokenTree::Ident(ident) => !is_keyword(ident),
+                        _ => true,
+                    };
+                }
+            }
+            if angle_depth == 0 && type_pos {
+                type_pos = matches!(tt, TokenTree::Ident(_))
+                    || matches!(punct, Some((':' | '&' | '*' | '\'' | '<' | '>', _)));
+            }
+            if let TokenTree::Ident(ident) = &tt {
+                if ident == "as" {
+                    type_pos = true;
+                }
+            }
+            after_ident = matches!(tt, TokenTree::Ident(_));
+            prev = [prev[1], punct];
+            tokens.extend([tt]);
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if `ident` is a keyword that cannot be the end of an
+    /// operand.
+    pub(crate) fn is_keyword(ident: &Ident) -> bool {
+        const KEYWORDS: &[&str] = &[
+            "abstract", "as", "async", "become", "box", "break", "const", "continue", "do", "dyn",
+            "else", "enum", "extern", "final", "fn", "for", "if", "impl", "in", "let", "loop",
+            "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+            "static", "struct", "trait", "try", "type", "typeof", "unsafe", "unsized", "use",
+            "virtual", "where", "while", "yield",
+        ];
+        KEYWORDS.iter().any(|kw| ident == kw)
+    }
+
+    fn brace(input: ParseStream<'_>, tokens: &mut TokenStream) -> Result<()> {
+        if input.peek(token::Brace) {
+            tokens.extend([input.parse::<TokenTree>()?]);
+            Ok(())
+        } else {
+            Err(input.error("expected `{`"))
+        }
+    }
+
+    fn is_brace(tt: &TokenTree) -> bool {
+        match tt {
+            TokenTree::Group(g) => g.delimiter() == Delimiter::Brace,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(feature = "printing")]
+mod printing {
+    use proc_macro2::TokenStream;
+    use quote::{ToTokens, TokenStreamExt as _};
+
+    use super::{Local, Stmt, StmtMacro};
+
+    impl ToTokens for Stmt {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            match self {
+                Stmt::Local(local) => local.to_tokens(tokens),
+                Stmt::Item(item) => item.to_tokens(tokens),
+                Stmt::Expr(expr, semi) => {
+                    expr.to_tokens(tokens);
+                    semi.to_tokens(tokens);
+                }
+                Stmt::Macro(mac) => mac.to_tokens(tokens),
+            }
+        }
+    }
+
+    impl ToTokens for Local {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            tokens.append_all(&self.attrs);
+            self.let_token.to_tokens(tokens);
+            self.pat.to_tokens(tokens);
+            if let Some(init) = &self.init {
+                init.eq_token.to_tokens(tokens);
+                init.expr.to_tokens(tokens);
+                if let Some((else_token, diverge)) = &init.diverge {
+                    else_token.to_tokens(tokens);
+                    diverge.to_tokens(tokens);
+                }
+            }
+            self.semi_token.to_tokens(tokens);
+        }
+    }
+
+    impl ToTokens for StmtMacro {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            tokens.append_all(&self.attrs);
+            self.mac.to_tokens(tokens);
+            self.semi_token.to_tokens(tokens);
+        }
+    }
+}
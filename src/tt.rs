@@ -0,0 +1,24 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Helpers for the extra traits of the data structures that contain unparsed
+// tokens.
+
+use alloc::string::ToString as _;
+use core::hash::{Hash, Hasher};
+
+use proc_macro2::TokenStream;
+
+/// Compares and hashes token streams by their string form, ignoring spans.
+pub(crate) struct TokenStreamHelper<'a>(pub(crate) &'a TokenStream);
+
+impl PartialEq for TokenStreamHelper<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_string() == other.0.to_string()
+    }
+}
+
+impl Hash for TokenStreamHelper<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_string().hash(state);
+    }
+}
@@ -0,0 +1,23 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Based on https://github.com/dtolnay/syn/blob/2.0.37/src/verbatim.rs.
+
+use proc_macro2::TokenStream;
+use syn::parse::ParseStream;
+
+/// Returns the tokens that `end` has consumed since it was forked from
+/// `begin`.
+///
+/// `end` must be at the same nesting level as `begin`, which is the case when
+/// the tokens in between were consumed by parsing.
+pub(crate) fn between<'a>(begin: ParseStream<'a>, end: ParseStream<'a>) -> TokenStream {
+    let end = end.cursor();
+    let mut cursor = begin.cursor();
+    let mut tokens = TokenStream::new();
+    while cursor != end {
+        let Some((tt, next)) = cursor.token_tree() else { break };
+        tokens.extend([tt]);
+        cursor = next;
+    }
+    tokens
+}
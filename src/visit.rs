@@ -0,0 +1,564 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Syntax tree traversal to walk a shared borrow of a syntax tree.
+//!
+//! Each method of the [`Visit`] trait is a hook that can be overridden to
+//! customize the behavior when visiting the corresponding type of node. By
+//! default, every method recursively visits the substructure of the input by
+//! invoking the right visitor method of each of its fields.
+//!
+//! The types from syn that the data structures of this crate contain, such as
+//! [`Type`](syn::Type) and [`Generics`](syn::Generics), are traversed with
+//! [`syn::visit`], and the nodes of those types nested in them are passed back
+//! to the methods of this trait. Tokens that are not parsed, such as
+//! [`Block::stmts`](crate::Block::stmts), are not visited.
+//!
+//! # Examples
+//!
+#![cfg_attr(not(feature = "parsing"), doc = "```ignore")]
+#![cfg_attr(feature = "parsing", doc = "```")]
+//! use syn_mid::{ItemFn, PatIdent, visit::{self, Visit}};
+//!
+//! struct Bindings(Vec<String>);
+//!
+//! impl<'ast> Visit<'ast> for Bindings {
+//!     fn visit_pat_ident(&mut self, node: &'ast PatIdent) {
+//!         self.0.push(node.ident.to_string());
+//!         visit::visit_pat_ident(self, node);
+//!     }
+//! }
+//!
+//! let item: ItemFn = syn::parse_quote! {
+//!     fn f((a, b): (u8, u8), Point { x, .. }: Point) {}
+//! };
+//! let mut v = Bindings(Vec::new());
+//! v.visit_item_fn(&item);
+//! assert_eq!(v.0, ["a", "b", "x"]);
+//! ```
+
+/// Syntax tree traversal to walk a shared borrow of a syntax tree.
+///
+/// See the [module documentation] for details.
+///
+/// [module documentation]: self
+pub trait Visit<'ast> {
+    fn visit_abi(&mut self, i: &'ast syn::Abi) {
+        visit_abi(self, i);
+    }
+    fn visit_arm(&mut self, i: &'ast crate::Arm) {
+        visit_arm(self, i);
+    }
+    fn visit_attribute(&mut self, i: &'ast syn::Attribute) {
+        visit_attribute(self, i);
+    }
+    fn visit_block(&mut self, i: &'ast crate::Block) {
+        visit_block(self, i);
+    }
+    fn visit_expr_lit(&mut self, i: &'ast syn::ExprLit) {
+        visit_expr_lit(self, i);
+    }
+    fn visit_expr_match(&mut self, i: &'ast crate::ExprMatch) {
+        visit_expr_match(self, i);
+    }
+    fn visit_expr_path(&mut self, i: &'ast syn::ExprPath) {
+        visit_expr_path(self, i);
+    }
+    fn visit_field_pat(&mut self, i: &'ast crate::FieldPat) {
+        visit_field_pat(self, i);
+    }
+    fn visit_fn_arg(&mut self, i: &'ast crate::FnArg) {
+        visit_fn_arg(self, i);
+    }
+    fn visit_generics(&mut self, i: &'ast syn::Generics) {
+        visit_generics(self, i);
+    }
+    fn visit_ident(&mut self, i: &'ast syn::Ident) {
+        visit_ident(self, i);
+    }
+    fn visit_item_fn(&mut self, i: &'ast crate::ItemFn) {
+        visit_item_fn(self, i);
+    }
+    fn visit_lifetime(&mut self, i: &'ast syn::Lifetime) {
+        visit_lifetime(self, i);
+    }
+    fn visit_local(&mut self, i: &'ast crate::Local) {
+        visit_local(self, i);
+    }
+    fn visit_local_init(&mut self, i: &'ast crate::LocalInit) {
+        visit_local_init(self, i);
+    }
+    fn visit_macro(&mut self, i: &'ast syn::Macro) {
+        visit_macro(self, i);
+    }
+    fn visit_member(&mut self, i: &'ast syn::Member) {
+        visit_member(self, i);
+    }
+    fn visit_pat(&mut self, i: &'ast crate::Pat) {
+        visit_pat(self, i);
+    }
+    fn visit_pat_ident(&mut self, i: &'ast crate::PatIdent) {
+        visit_pat_ident(self, i);
+    }
+    fn visit_pat_or(&mut self, i: &'ast crate::PatOr) {
+        visit_pat_or(self, i);
+    }
+    fn visit_pat_reference(&mut self, i: &'ast crate::PatReference) {
+        visit_pat_reference(self, i);
+    }
+    fn visit_pat_rest(&mut self, i: &'ast crate::PatRest) {
+        visit_pat_rest(self, i);
+    }
+    fn visit_pat_struct(&mut self, i: &'ast crate::PatStruct) {
+        visit_pat_struct(self, i);
+    }
+    fn visit_pat_tuple(&mut self, i: &'ast crate::PatTuple) {
+        visit_pat_tuple(self, i);
+    }
+    fn visit_pat_tuple_struct(&mut self, i: &'ast crate::PatTupleStruct) {
+        visit_pat_tuple_struct(self, i);
+    }
+    fn visit_pat_type(&mut self, i: &'ast crate::PatType) {
+        visit_pat_type(self, i);
+    }
+    fn visit_pat_wild(&mut self, i: &'ast crate::PatWild) {
+        visit_pat_wild(self, i);
+    }
+    fn visit_path(&mut self, i: &'ast syn::Path) {
+        visit_path(self, i);
+    }
+    fn visit_receiver(&mut self, i: &'ast crate::Receiver) {
+        visit_receiver(self, i);
+    }
+    fn visit_return_type(&mut self, i: &'ast syn::ReturnType) {
+        visit_return_type(self, i);
+    }
+    fn visit_signature(&mut self, i: &'ast crate::Signature) {
+        visit_signature(self, i);
+    }
+    fn visit_stmt(&mut self, i: &'ast crate::Stmt) {
+        visit_stmt(self, i);
+    }
+    fn visit_stmt_macro(&mut self, i: &'ast crate::StmtMacro) {
+        visit_stmt_macro(self, i);
+    }
+    fn visit_type(&mut self, i: &'ast syn::Type) {
+        visit_type(self, i);
+    }
+    fn visit_variadic(&mut self, i: &'ast crate::Variadic) {
+        visit_variadic(self, i);
+    }
+    fn visit_visibility(&mut self, i: &'ast syn::Visibility) {
+        visit_visibility(self, i);
+    }
+}
+
+pub fn visit_abi<'ast, V>(v: &mut V, node: &'ast syn::Abi)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    syn::visit::visit_abi(&mut Syn(v), node);
+}
+
+pub fn visit_arm<'ast, V>(v: &mut V, node: &'ast crate::Arm)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.attrs {
+        v.visit_attribute(it);
+    }
+    v.visit_pat(&node.pat);
+}
+
+pub fn visit_attribute<'ast, V>(v: &mut V, node: &'ast syn::Attribute)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    syn::visit::visit_attribute(&mut Syn(v), node);
+}
+
+pub fn visit_block<'ast, V>(v: &mut V, node: &'ast crate::Block)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.inner_attrs {
+        v.visit_attribute(it);
+    }
+}
+
+pub fn visit_expr_lit<'ast, V>(v: &mut V, node: &'ast syn::ExprLit)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    syn::visit::visit_expr_lit(&mut Syn(v), node);
+}
+
+pub fn visit_expr_match<'ast, V>(v: &mut V, node: &'ast crate::ExprMatch)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.attrs {
+        v.visit_attribute(it);
+    }
+    for it in &node.arms {
+        v.visit_arm(it);
+    }
+}
+
+pub fn visit_expr_path<'ast, V>(v: &mut V, node: &'ast syn::ExprPath)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    syn::visit::visit_expr_path(&mut Syn(v), node);
+}
+
+pub fn visit_field_pat<'ast, V>(v: &mut V, node: &'ast crate::FieldPat)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.attrs {
+        v.visit_attribute(it);
+    }
+    v.visit_member(&node.member);
+    v.visit_pat(&node.pat);
+}
+
+pub fn visit_fn_arg<'ast, V>(v: &mut V, node: &'ast crate::FnArg)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    match node {
+        crate::FnArg::Receiver(it) => v.visit_receiver(it),
+        crate::FnArg::Typed(it) => v.visit_pat_type(it),
+    }
+}
+
+pub fn visit_generics<'ast, V>(v: &mut V, node: &'ast syn::Generics)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    syn::visit::visit_generics(&mut Syn(v), node);
+}
+
+pub fn visit_ident<'ast, V>(v: &mut V, node: &'ast syn::Ident)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    syn::visit::visit_ident(&mut Syn(v), node);
+}
+
+pub fn visit_item_fn<'ast, V>(v: &mut V, node: &'ast crate::ItemFn)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.attrs {
+        v.visit_attribute(it);
+    }
+    v.visit_visibility(&node.vis);
+    v.visit_signature(&node.sig);
+    v.visit_block(&node.block);
+}
+
+pub fn visit_lifetime<'ast, V>(v: &mut V, node: &'ast syn::Lifetime)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    syn::visit::visit_lifetime(&mut Syn(v), node);
+}
+
+pub fn visit_local<'ast, V>(v: &mut V, node: &'ast crate::Local)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.attrs {
+        v.visit_attribute(it);
+    }
+    v.visit_pat(&node.pat);
+    if let Some(it) = &node.init {
+        v.visit_local_init(it);
+    }
+}
+
+pub fn visit_local_init<'ast, V>(v: &mut V, node: &'ast crate::LocalInit)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    if let Some((_, block)) = &node.diverge {
+        v.visit_block(block);
+    }
+}
+
+pub fn visit_macro<'ast, V>(v: &mut V, node: &'ast syn::Macro)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    syn::visit::visit_macro(&mut Syn(v), node);
+}
+
+pub fn visit_member<'ast, V>(v: &mut V, node: &'ast syn::Member)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    syn::visit::visit_member(&mut Syn(v), node);
+}
+
+pub fn visit_pat<'ast, V>(v: &mut V, node: &'ast crate::Pat)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    match node {
+        crate::Pat::Ident(it) => v.visit_pat_ident(it),
+        crate::Pat::Lit(it) => v.visit_expr_lit(it),
+        crate::Pat::Or(it) => v.visit_pat_or(it),
+        crate::Pat::Path(it) => v.visit_expr_path(it),
+        crate::Pat::Reference(it) => v.visit_pat_reference(it),
+        crate::Pat::Rest(it) => v.visit_pat_rest(it),
+        crate::Pat::Struct(it) => v.visit_pat_struct(it),
+        crate::Pat::Tuple(it) => v.visit_pat_tuple(it),
+        crate::Pat::TupleStruct(it) => v.visit_pat_tuple_struct(it),
+        crate::Pat::Type(it) => v.visit_pat_type(it),
+        crate::Pat::Verbatim(_) => {}
+        crate::Pat::Wild(it) => v.visit_pat_wild(it),
+    }
+}
+
+pub fn visit_pat_ident<'ast, V>(v: &mut V, node: &'ast crate::PatIdent)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.attrs {
+        v.visit_attribute(it);
+    }
+    v.visit_ident(&node.ident);
+}
+
+pub fn visit_pat_or<'ast, V>(v: &mut V, node: &'ast crate::PatOr)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.attrs {
+        v.visit_attribute(it);
+    }
+    for it in &node.cases {
+        v.visit_pat(it);
+    }
+}
+
+pub fn visit_pat_reference<'ast, V>(v: &mut V, node: &'ast crate::PatReference)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.attrs {
+        v.visit_attribute(it);
+    }
+    v.visit_pat(&node.pat);
+}
+
+pub fn visit_pat_rest<'ast, V>(v: &mut V, node: &'ast crate::PatRest)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.attrs {
+        v.visit_attribute(it);
+    }
+}
+
+pub fn visit_pat_struct<'ast, V>(v: &mut V, node: &'ast crate::PatStruct)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.attrs {
+        v.visit_attribute(it);
+    }
+    v.visit_path(&node.path);
+    for it in &node.fields {
+        v.visit_field_pat(it);
+    }
+    if let Some(it) = &node.rest {
+        v.visit_pat_rest(it);
+    }
+}
+
+pub fn visit_pat_tuple<'ast, V>(v: &mut V, node: &'ast crate::PatTuple)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.attrs {
+        v.visit_attribute(it);
+    }
+    for it in &node.elems {
+        v.visit_pat(it);
+    }
+}
+
+pub fn visit_pat_tuple_struct<'ast, V>(v: &mut V, node: &'ast crate::PatTupleStruct)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.attrs {
+        v.visit_attribute(it);
+    }
+    v.visit_path(&node.path);
+    for it in &node.elems {
+        v.visit_pat(it);
+    }
+}
+
+pub fn visit_pat_type<'ast, V>(v: &mut V, node: &'ast crate::PatType)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.attrs {
+        v.visit_attribute(it);
+    }
+    v.visit_pat(&node.pat);
+    v.visit_type(&node.ty);
+}
+
+pub fn visit_pat_wild<'ast, V>(v: &mut V, node: &'ast crate::PatWild)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.attrs {
+        v.visit_attribute(it);
+    }
+}
+
+pub fn visit_path<'ast, V>(v: &mut V, node: &'ast syn::Path)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    syn::visit::visit_path(&mut Syn(v), node);
+}
+
+pub fn visit_receiver<'ast, V>(v: &mut V, node: &'ast crate::Receiver)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.attrs {
+        v.visit_attribute(it);
+    }
+    if let Some((_, Some(lifetime))) = &node.reference {
+        v.visit_lifetime(lifetime);
+    }
+    v.visit_type(&node.ty);
+}
+
+pub fn visit_return_type<'ast, V>(v: &mut V, node: &'ast syn::ReturnType)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    syn::visit::visit_return_type(&mut Syn(v), node);
+}
+
+pub fn visit_signature<'ast, V>(v: &mut V, node: &'ast crate::Signature)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    if let Some(it) = &node.abi {
+        v.visit_abi(it);
+    }
+    v.visit_ident(&node.ident);
+    v.visit_generics(&node.generics);
+    for it in &node.inputs {
+        v.visit_fn_arg(it);
+    }
+    if let Some(it) = &node.variadic {
+        v.visit_variadic(it);
+    }
+    v.visit_return_type(&node.output);
+}
+
+pub fn visit_stmt<'ast, V>(v: &mut V, node: &'ast crate::Stmt)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    match node {
+        crate::Stmt::Local(it) => v.visit_local(it),
+        crate::Stmt::Macro(it) => v.visit_stmt_macro(it),
+        // Not parsed.
+        crate::Stmt::Item(..) | crate::Stmt::Expr(..) => {}
+    }
+}
+
+pub fn visit_stmt_macro<'ast, V>(v: &mut V, node: &'ast crate::StmtMacro)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.attrs {
+        v.visit_attribute(it);
+    }
+    v.visit_macro(&node.mac);
+}
+
+pub fn visit_type<'ast, V>(v: &mut V, node: &'ast syn::Type)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    syn::visit::visit_type(&mut Syn(v), node);
+}
+
+pub fn visit_variadic<'ast, V>(v: &mut V, node: &'ast crate::Variadic)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.attrs {
+        v.visit_attribute(it);
+    }
+    if let Some((pat, _)) = &node.pat {
+        v.visit_pat(pat);
+    }
+}
+
+pub fn visit_visibility<'ast, V>(v: &mut V, node: &'ast syn::Visibility)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    syn::visit::visit_visibility(&mut Syn(v), node);
+}
+
+/// Passes the nodes of the types of syn back to a [`Visit`].
+struct Syn<'a, V: ?Sized>(&'a mut V);
+impl<'ast, V> syn::visit::Visit<'ast> for Syn<'_, V>
+where
+    V: Visit<'ast> + ?Sized,
+{
+    fn visit_abi(&mut self, i: &'ast syn::Abi) {
+        self.0.visit_abi(i);
+    }
+    fn visit_attribute(&mut self, i: &'ast syn::Attribute) {
+        self.0.visit_attribute(i);
+    }
+    fn visit_expr_lit(&mut self, i: &'ast syn::ExprLit) {
+        self.0.visit_expr_lit(i);
+    }
+    fn visit_expr_path(&mut self, i: &'ast syn::ExprPath) {
+        self.0.visit_expr_path(i);
+    }
+    fn visit_generics(&mut self, i: &'ast syn::Generics) {
+        self.0.visit_generics(i);
+    }
+    fn visit_ident(&mut self, i: &'ast syn::Ident) {
+        self.0.visit_ident(i);
+    }
+    fn visit_lifetime(&mut self, i: &'ast syn::Lifetime) {
+        self.0.visit_lifetime(i);
+    }
+    fn visit_macro(&mut self, i: &'ast syn::Macro) {
+        self.0.visit_macro(i);
+    }
+    fn visit_member(&mut self, i: &'ast syn::Member) {
+        self.0.visit_member(i);
+    }
+    fn visit_path(&mut self, i: &'ast syn::Path) {
+        self.0.visit_path(i);
+    }
+    fn visit_return_type(&mut self, i: &'ast syn::ReturnType) {
+        self.0.visit_return_type(i);
+    }
+    fn visit_type(&mut self, i: &'ast syn::Type) {
+        self.0.visit_type(i);
+    }
+    fn visit_visibility(&mut self, i: &'ast syn::Visibility) {
+        self.0.visit_visibility(i);
+    }
+}
@@ -0,0 +1,575 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Syntax tree traversal to mutate an exclusive borrow of a syntax tree in
+//! place.
+//!
+//! Each method of the [`VisitMut`] trait is a hook that can be overridden to
+//! customize the behavior when mutating the corresponding type of node. By
+//! default, every method recursively visits the substructure of the input by
+//! invoking the right visitor method of each of its fields.
+//!
+//! The types from syn that the data structures of this crate contain, such as
+//! [`Type`](syn::Type) and [`Generics`](syn::Generics), are traversed with
+//! [`syn::visit_mut`], and the nodes of those types nested in them are passed
+//! back to the methods of this trait. Tokens that are not parsed, such as
+//! [`Block::stmts`](crate::Block::stmts), are not visited.
+//!
+//! # Examples
+//!
+#![cfg_attr(not(all(feature = "parsing", feature = "printing")), doc = "```ignore")]
+#![cfg_attr(all(feature = "parsing", feature = "printing"), doc = "```")]
+//! use quote::quote;
+//! use syn_mid::{ItemFn, visit_mut::VisitMut};
+//!
+//! // Replaces `String` with `Box<str>`.
+//! struct BoxStr;
+//!
+//! impl VisitMut for BoxStr {
+//!     fn visit_type_mut(&mut self, node: &mut syn::Type) {
+//!         if let syn::Type::Path(ty) = node {
+//!             if ty.qself.is_none() && ty.path.is_ident("String") {
+//!                 *node = syn::parse_quote!(Box<str>);
+//!                 return;
+//!             }
+//!         }
+//!         syn_mid::visit_mut::visit_type_mut(self, node);
+//!     }
+//! }
+//!
+//! let mut item: ItemFn = syn::parse_quote! {
+//!     fn f(s: String, v: Vec<String>) -> Option<String> {}
+//! };
+//! BoxStr.visit_item_fn_mut(&mut item);
+//! let sig = &item.sig;
+//! assert_eq!(
+//!     quote!(#sig).to_string(),
+//!     quote!(fn f(s: Box<str>, v: Vec<Box<str> >) -> Option<Box<str> >).to_string(),
+//! );
+//! ```
+
+/// Syntax tree traversal to mutate an exclusive borrow of a syntax tree in place.
+///
+/// See the [module documentation] for details.
+///
+/// [module documentation]: self
+pub trait VisitMut {
+    fn visit_abi_mut(&mut self, i: &mut syn::Abi) {
+        visit_abi_mut(self, i);
+    }
+    fn visit_arm_mut(&mut self, i: &mut crate::Arm) {
+        visit_arm_mut(self, i);
+    }
+    fn visit_attribute_mut(&mut self, i: &mut syn::Attribute) {
+        visit_attribute_mut(self, i);
+    }
+    fn visit_block_mut(&mut self, i: &mut crate::Block) {
+        visit_block_mut(self, i);
+    }
+    fn visit_expr_lit_mut(&mut self, i: &mut syn::ExprLit) {
+        visit_expr_lit_mut(self, i);
+    }
+    fn visit_expr_match_mut(&mut self, i: &mut crate::ExprMatch) {
+        visit_expr_match_mut(self, i);
+    }
+    fn visit_expr_path_mut(&mut self, i: &mut syn::ExprPath) {
+        visit_expr_path_mut(self, i);
+    }
+    fn visit_field_pat_mut(&mut self, i: &mut crate::FieldPat) {
+        visit_field_pat_mut(self, i);
+    }
+    fn visit_fn_arg_mut(&mut self, i: &mut crate::FnArg) {
+        visit_fn_arg_mut(self, i);
+    }
+    fn visit_generics_mut(&mut self, i: &mut syn::Generics) {
+        visit_generics_mut(self, i);
+    }
+    fn visit_ident_mut(&mut self, i: &mut syn::Ident) {
+        visit_ident_mut(self, i);
+    }
+    fn visit_item_fn_mut(&mut self, i: &mut crate::ItemFn) {
+        visit_item_fn_mut(self, i);
+    }
+    fn visit_lifetime_mut(&mut self, i: &mut syn::Lifetime) {
+        visit_lifetime_mut(self, i);
+    }
+    fn visit_local_mut(&mut self, i: &mut crate::Local) {
+        visit_local_mut(self, i);
+    }
+    fn visit_local_init_mut(&mut self, i: &mut crate::LocalInit) {
+        visit_local_init_mut(self, i);
+    }
+    fn visit_macro_mut(&mut self, i: &mut syn::Macro) {
+        visit_macro_mut(self, i);
+    }
+    fn visit_member_mut(&mut self, i: &mut syn::Member) {
+        visit_member_mut(self, i);
+    }
+    fn visit_pat_mut(&mut self, i: &mut crate::Pat) {
+        visit_pat_mut(self, i);
+    }
+    fn visit_pat_ident_mut(&mut self, i: &mut crate::PatIdent) {
+        visit_pat_ident_mut(self, i);
+    }
+    fn visit_pat_or_mut(&mut self, i: &mut crate::PatOr) {
+        visit_pat_or_mut(self, i);
+    }
+    fn visit_pat_reference_mut(&mut self, i: &mut crate::PatReference) {
+        visit_pat_reference_mut(self, i);
+    }
+    fn visit_pat_rest_mut(&mut self, i: &mut crate::PatRest) {
+        visit_pat_rest_mut(self, i);
+    }
+    fn visit_pat_struct_mut(&mut self, i: &mut crate::PatStruct) {
+        visit_pat_struct_mut(self, i);
+    }
+    fn visit_pat_tuple_mut(&mut self, i: &mut crate::PatTuple) {
+        visit_pat_tuple_mut(self, i);
+    }
+    fn visit_pat_tuple_struct_mut(&mut self, i: &mut crate::PatTupleStruct) {
+        visit_pat_tuple_struct_mut(self, i);
+    }
+    fn visit_pat_type_mut(&mut self, i: &mut crate::PatType) {
+        visit_pat_type_mut(self, i);
+    }
+    fn visit_pat_wild_mut(&mut self, i: &mut crate::PatWild) {
+        visit_pat_wild_mut(self, i);
+    }
+    fn visit_path_mut(&mut self, i: &mut syn::Path) {
+        visit_path_mut(self, i);
+    }
+    fn visit_receiver_mut(&mut self, i: &mut crate::Receiver) {
+        visit_receiver_mut(self, i);
+    }
+    fn visit_return_type_mut(&mut self, i: &mut syn::ReturnType) {
+        visit_return_type_mut(self, i);
+    }
+    fn visit_signature_mut(&mut self, i: &mut crate::Signature) {
+        visit_signature_mut(self, i);
+    }
+    fn visit_stmt_mut(&mut self, i: &mut crate::Stmt) {
+        visit_stmt_mut(self, i);
+    }
+    fn visit_stmt_macro_mut(&mut self, i: &mut crate::StmtMacro) {
+        visit_stmt_macro_mut(self, i);
+    }
+    fn visit_type_mut(&mut self, i: &mut syn::Type) {
+        visit_type_mut(self, i);
+    }
+    fn visit_variadic_mut(&mut self, i: &mut crate::Variadic) {
+        visit_variadic_mut(self, i);
+    }
+    fn visit_visibility_mut(&mut self, i: &mut syn::Visibility) {
+        visit_visibility_mut(self, i);
+    }
+}
+
+pub fn visit_abi_mut<V>(v: &mut V, node: &mut syn::Abi)
+where
+    V: VisitMut + ?Sized,
+{
+    syn::visit_mut::visit_abi_mut(&mut Syn(v), node);
+}
+
+pub fn visit_arm_mut<V>(v: &mut V, node: &mut crate::Arm)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.attrs {
+        v.visit_attribute_mut(it);
+    }
+    v.visit_pat_mut(&mut node.pat);
+}
+
+pub fn visit_attribute_mut<V>(v: &mut V, node: &mut syn::Attribute)
+where
+    V: VisitMut + ?Sized,
+{
+    syn::visit_mut::visit_attribute_mut(&mut Syn(v), node);
+}
+
+pub fn visit_block_mut<V>(v: &mut V, node: &mut crate::Block)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.inner_attrs {
+        v.visit_attribute_mut(it);
+    }
+}
+
+pub fn visit_expr_lit_mut<V>(v: &mut V, node: &mut syn::ExprLit)
+where
+    V: VisitMut + ?Sized,
+{
+    syn::visit_mut::visit_expr_lit_mut(&mut Syn(v), node);
+}
+
+pub fn visit_expr_match_mut<V>(v: &mut V, node: &mut crate::ExprMatch)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.attrs {
+        v.visit_attribute_mut(it);
+    }
+    for it in &mut node.arms {
+        v.visit_arm_mut(it);
+    }
+}
+
+pub fn visit_expr_path_mut<V>(v: &mut V, node: &mut syn::ExprPath)
+where
+    V: VisitMut + ?Sized,
+{
+    syn::visit_mut::visit_expr_path_mut(&mut Syn(v), node);
+}
+
+pub fn visit_field_pat_mut<V>(v: &mut V, node: &mut crate::FieldPat)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.attrs {
+        v.visit_attribute_mut(it);
+    }
+    v.visit_member_mut(&mut node.member);
+    v.visit_pat_mut(&mut node.pat);
+}
+
+pub fn visit_fn_arg_mut<V>(v: &mut V, node: &mut crate::FnArg)
+where
+    V: VisitMut + ?Sized,
+{
+    match node {
+        crate::FnArg::Receiver(it) => v.visit_receiver_mut(it),
+        crate::FnArg::Typed(it) => v.visit_pat_type_mut(it),
+    }
+}
+
+pub fn visit_generics_mut<V>(v: &mut V, node: &mut syn::Generics)
+where
+    V: VisitMut + ?Sized,
+{
+    syn::visit_mut::visit_generics_mut(&mut Syn(v), node);
+}
+
+pub fn visit_ident_mut<V>(v: &mut V, node: &mut syn::Ident)
+where
+    V: VisitMut + ?Sized,
+{
+    syn::visit_mut::visit_ident_mut(&mut Syn(v), node);
+}
+
+pub fn visit_item_fn_mut<V>(v: &mut V, node: &mut crate::ItemFn)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.attrs {
+        v.visit_attribute_mut(it);
+    }
+    v.visit_visibility_mut(&mut node.vis);
+    v.visit_signature_mut(&mut node.sig);
+    v.visit_block_mut(&mut node.block);
+}
+
+pub fn visit_lifetime_mut<V>(v: &mut V, node: &mut syn::Lifetime)
+where
+    V: VisitMut + ?Sized,
+{
+    syn::visit_mut::visit_lifetime_mut(&mut Syn(v), node);
+}
+
+pub fn visit_local_mut<V>(v: &mut V, node: &mut crate::Local)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.attrs {
+        v.visit_attribute_mut(it);
+    }
+    v.visit_pat_mut(&mut node.pat);
+    if let Some(it) = &mut node.init {
+        v.visit_local_init_mut(it);
+    }
+}
+
+pub fn visit_local_init_mut<V>(v: &mut V, node: &mut crate::LocalInit)
+where
+    V: VisitMut + ?Sized,
+{
+    if let Some((_, block)) = &mut node.diverge {
+        v.visit_block_mut(block);
+    }
+}
+
+pub fn visit_macro_mut<V>(v: &mut V, node: &mut syn::Macro)
+where
+    V: VisitMut + ?Sized,
+{
+    syn::visit_mut::visit_macro_mut(&mut Syn(v), node);
+}
+
+pub fn visit_member_mut<V>(v: &mut V, node: &mut syn::Member)
+where
+    V: VisitMut + ?Sized,
+{
+    syn::visit_mut::visit_member_mut(&mut Syn(v), node);
+}
+
+pub fn visit_pat_mut<V>(v: &mut V, node: &mut crate::Pat)
+where
+    V: VisitMut + ?Sized,
+{
+    match node {
+        crate::Pat::Ident(it) => v.visit_pat_ident_mut(it),
+        crate::Pat::Lit(it) => v.visit_expr_lit_mut(it),
+        crate::Pat::Or(it) => v.visit_pat_or_mut(it),
+        crate::Pat::Path(it) => v.visit_expr_path_mut(it),
+        crate::Pat::Reference(it) => v.visit_pat_reference_mut(it),
+        crate::Pat::Rest(it) => v.visit_pat_rest_mut(it),
+        crate::Pat::Struct(it) => v.visit_pat_struct_mut(it),
+        crate::Pat::Tuple(it) => v.visit_pat_tuple_mut(it),
+        crate::Pat::TupleStruct(it) => v.visit_pat_tuple_struct_mut(it),
+        crate::Pat::Type(it) => v.visit_pat_type_mut(it),
+        crate::Pat::Verbatim(_) => {}
+        crate::Pat::Wild(it) => v.visit_pat_wild_mut(it),
+    }
+}
+
+pub fn visit_pat_ident_mut<V>(v: &mut V, node: &mut crate::PatIdent)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.attrs {
+        v.visit_attribute_mut(it);
+    }
+    v.visit_ident_mut(&mut node.ident);
+}
+
+pub fn visit_pat_or_mut<V>(v: &mut V, node: &mut crate::PatOr)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.attrs {
+        v.visit_attribute_mut(it);
+    }
+    for it in &mut node.cases {
+        v.visit_pat_mut(it);
+    }
+}
+
+pub fn visit_pat_reference_mut<V>(v: &mut V, node: &mut crate::PatReference)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.attrs {
+        v.visit_attribute_mut(it);
+    }
+    v.visit_pat_mut(&mut node.pat);
+}
+
+pub fn visit_pat_rest_mut<V>(v: &mut V, node: &mut crate::PatRest)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.attrs {
+        v.visit_attribute_mut(it);
+    }
+}
+
+pub fn visit_pat_struct_mut<V>(v: &mut V, node: &mut crate::PatStruct)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.attrs {
+        v.visit_attribute_mut(it);
+    }
+    v.visit_path_mut(&mut node.path);
+    for it in &mut node.fields {
+        v.visit_field_pat_mut(it);
+    }
+    if let Some(it) = &mut node.rest {
+        v.visit_pat_rest_mut(it);
+    }
+}
+
+pub fn visit_pat_tuple_mut<V>(v: &mut V, node: &mut crate::PatTuple)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.attrs {
+        v.visit_attribute_mut(it);
+    }
+    for it in &mut node.elems {
+        v.visit_pat_mut(it);
+    }
+}
+
+pub fn visit_pat_tuple_struct_mut<V>(v: &mut V, node: &mut crate::PatTupleStruct)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.attrs {
+        v.visit_attribute_mut(it);
+    }
+    v.visit_path_mut(&mut node.path);
+    for it in &mut node.elems {
+        v.visit_pat_mut(it);
+    }
+}
+
+pub fn visit_pat_type_mut<V>(v: &mut V, node: &mut crate::PatType)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.attrs {
+        v.visit_attribute_mut(it);
+    }
+    v.visit_pat_mut(&mut node.pat);
+    v.visit_type_mut(&mut node.ty);
+}
+
+pub fn visit_pat_wild_mut<V>(v: &mut V, node: &mut crate::PatWild)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.attrs {
+        v.visit_attribute_mut(it);
+    }
+}
+
+pub fn visit_path_mut<V>(v: &mut V, node: &mut syn::Path)
+where
+    V: VisitMut + ?Sized,
+{
+    syn::visit_mut::visit_path_mut(&mut Syn(v), node);
+}
+
+pub fn visit_receiver_mut<V>(v: &mut V, node: &mut crate::Receiver)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.attrs {
+        v.visit_attribute_mut(it);
+    }
+    if let Some((_, Some(lifetime))) = &mut node.reference {
+        v.visit_lifetime_mut(lifetime);
+    }
+    v.visit_type_mut(&mut node.ty);
+}
+
+pub fn visit_return_type_mut<V>(v: &mut V, node: &mut syn::ReturnType)
+where
+    V: VisitMut + ?Sized,
+{
+    syn::visit_mut::visit_return_type_mut(&mut Syn(v), node);
+}
+
+pub fn visit_signature_mut<V>(v: &mut V, node: &mut crate::Signature)
+where
+    V: VisitMut + ?Sized,
+{
+    if let Some(it) = &mut node.abi {
+        v.visit_abi_mut(it);
+    }
+    v.visit_ident_mut(&mut node.ident);
+    v.visit_generics_mut(&mut node.generics);
+    for it in &mut node.inputs {
+        v.visit_fn_arg_mut(it);
+    }
+    if let Some(it) = &mut node.variadic {
+        v.visit_variadic_mut(it);
+    }
+    v.visit_return_type_mut(&mut node.output);
+}
+
+pub fn visit_stmt_mut<V>(v: &mut V, node: &mut crate::Stmt)
+where
+    V: VisitMut + ?Sized,
+{
+    match node {
+        crate::Stmt::Local(it) => v.visit_local_mut(it),
+        crate::Stmt::Macro(it) => v.visit_stmt_macro_mut(it),
+        // Not parsed.
+        crate::Stmt::Item(..) | crate::Stmt::Expr(..) => {}
+    }
+}
+
+pub fn visit_stmt_macro_mut<V>(v: &mut V, node: &mut crate::StmtMacro)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.attrs {
+        v.visit_attribute_mut(it);
+    }
+    v.visit_macro_mut(&mut node.mac);
+}
+
+pub fn visit_type_mut<V>(v: &mut V, node: &mut syn::Type)
+where
+    V: VisitMut + ?Sized,
+{
+    syn::visit_mut::visit_type_mut(&mut Syn(v), node);
+}
+
+pub fn visit_variadic_mut<V>(v: &mut V, node: &mut crate::Variadic)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.attrs {
+        v.visit_attribute_mut(it);
+    }
+    if let Some((pat, _)) = &mut node.pat {
+        v.visit_pat_mut(pat);
+    }
+}
+
+pub fn visit_visibility_mut<V>(v: &mut V, node: &mut syn::Visibility)
+where
+    V: VisitMut + ?Sized,
+{
+    syn::visit_mut::visit_visibility_mut(&mut Syn(v), node);
+}
+
+/// Passes the nodes of the types of syn back to a [`VisitMut`].
+struct Syn<'a, V: ?Sized>(&'a mut V);
+impl<V> syn::visit_mut::VisitMut for Syn<'_, V>
+where
+    V: VisitMut + ?Sized,
+{
+    fn visit_abi_mut(&mut self, i: &mut syn::Abi) {
+        self.0.visit_abi_mut(i);
+    }
+    fn visit_attribute_mut(&mut self, i: &mut syn::Attribute) {
+        self.0.visit_attribute_mut(i);
+    }
+    fn visit_expr_lit_mut(&mut self, i: &mut syn::ExprLit) {
+        self.0.visit_expr_lit_mut(i);
+    }
+    fn visit_expr_path_mut(&mut self, i: &mut syn::ExprPath) {
+        self.0.visit_expr_path_mut(i);
+    }
+    fn visit_generics_mut(&mut self, i: &mut syn::Generics) {
+        self.0.visit_generics_mut(i);
+    }
+    fn visit_ident_mut(&mut self, i: &mut syn::Ident) {
+        self.0.visit_ident_mut(i);
+    }
+    fn visit_lifetime_mut(&mut self, i: &mut syn::Lifetime) {
+        self.0.visit_lifetime_mut(i);
+    }
+    fn visit_macro_mut(&mut self, i: &mut syn::Macro) {
+        self.0.visit_macro_mut(i);
+    }
+    fn visit_member_mut(&mut self, i: &mut syn::Member) {
+        self.0.visit_member_mut(i);
+    }
+    fn visit_path_mut(&mut self, i: &mut syn::Path) {
+        self.0.visit_path_mut(i);
+    }
+    fn visit_return_type_mut(&mut self, i: &mut syn::ReturnType) {
+        self.0.visit_return_type_mut(i);
+    }
+    fn visit_type_mut(&mut self, i: &mut syn::Type) {
+        self.0.visit_type_mut(i);
+    }
+    fn visit_visibility_mut(&mut self, i: &mut syn::Visibility) {
+        self.0.visit_visibility_mut(i);
+    }
+}
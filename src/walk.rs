@@ -0,0 +1,187 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// A minimal mutable visitor over the type syntax tree of syn.
+//
+// syn's `VisitMut` requires its "visit-mut" feature, which this crate only
+// enables with its own optional `visit-mut` feature. The signature
+// transformations are available without it and only need to walk types, so
+// this mirrors the parts of `syn::visit_mut` that they use. Each `visit_*` method
+// defaults to the corresponding `walk_*` function, which visits the children.
+
+use syn::{
+    AngleBracketedGenericArguments, GenericArgument, Lifetime, ParenthesizedGenericArguments, Path,
+    PathArguments, ReturnType, Type, TypeBareFn, TypeImplTrait, TypeParamBound, TypePath,
+    TypeReference,
+};
+
+pub(crate) trait VisitTypeMut {
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        walk_type(self, ty);
+    }
+    fn visit_type_bare_fn_mut(&mut self, ty: &mut TypeBareFn) {
+        walk_type_bare_fn(self, ty);
+    }
+    fn visit_type_impl_trait_mut(&mut self, ty: &mut TypeImplTrait) {
+        walk_type_impl_trait(self, ty);
+    }
+    fn visit_type_path_mut(&mut self, ty: &mut TypePath) {
+        walk_type_path(self, ty);
+    }
+    fn visit_type_reference_mut(&mut self, ty: &mut TypeReference) {
+        walk_type_reference(self, ty);
+    }
+    fn visit_path_mut(&mut self, path: &mut Path) {
+        walk_path(self, path);
+    }
+    fn visit_parenthesized_generic_arguments_mut(
+        &mut self,
+        args: &mut ParenthesizedGenericArguments,
+    ) {
+        walk_parenthesized_generic_arguments(self, args);
+    }
+    fn visit_type_param_bound_mut(&mut self, bound: &mut TypeParamBound) {
+        walk_type_param_bound(self, bound);
+    }
+    fn visit_lifetime_mut(&mut self, _lifetime: &mut Lifetime) {}
+}
+
+pub(crate) fn walk_type<V>(v: &mut V, ty: &mut Type)
+where
+    V: VisitTypeMut + ?Sized,
+{
+    match ty {
+        Type::Array(ty) => v.visit_type_mut(&mut ty.elem),
+        Type::BareFn(ty) => v.visit_type_bare_fn_mut(ty),
+        Type::Group(ty) => v.visit_type_mut(&mut ty.elem),
+        Type::ImplTrait(ty) => v.visit_type_impl_trait_mut(ty),
+        Type::Paren(ty) => v.visit_type_mut(&mut ty.elem),
+        Type::Path(ty) => v.visit_type_path_mut(ty),
+        Type::Ptr(ty) => v.visit_type_mut(&mut ty.elem),
+        Type::Reference(ty) => v.visit_type_reference_mut(ty),
+        Type::Slice(ty) => v.visit_type_mut(&mut ty.elem),
+        Type::TraitObject(ty) => {
+            for bound in &mut ty.bounds {
+                v.visit_type_param_bound_mut(bound);
+            }
+        }
+        Type::Tuple(ty) => {
+            for elem in &mut ty.elems {
+                v.visit_type_mut(elem);
+            }
+        }
+        _ => {}
+    }
+}
+
+pub(crate) fn walk_type_bare_fn<V>(v: &mut V, ty: &mut TypeBareFn)
+where
+    V: VisitTypeMut + ?Sized,
+{
+    for arg in &mut ty.inputs {
+        v.visit_type_mut(&mut arg.ty);
+    }
+    walk_return_type(v, &mut ty.output);
+}
+
+pub(crate) fn walk_type_impl_trait<V>(v: &mut V, ty: &mut TypeImplTrait)
+where
+    V: VisitTypeMut + ?Sized,
+{
+    for bound in &mut ty.bounds {
+        v.visit_type_param_bound_mut(bound);
+    }
+}
+
+pub(crate) fn walk_type_path<V>(v: &mut V, ty: &mut TypePath)
+where
+    V: VisitTypeMut + ?Sized,
+{
+    if let Some(qself) = &mut ty.qself {
+        v.visit_type_mut(&mut qself.ty);
+    }
+    v.visit_path_mut(&mut ty.path);
+}
+
+pub(crate) fn walk_type_reference<V>(v: &mut V, ty: &mut TypeReference)
+where
+    V: VisitTypeMut + ?Sized,
+{
+    if let Some(lifetime) = &mut ty.lifetime {
+        v.visit_lifetime_mut(lifetime);
+    }
+    v.visit_type_mut(&mut ty.elem);
+}
+
+pub(crate) fn walk_path<V>(v: &mut V, path: &mut Path)
+where
+    V: VisitTypeMut + ?Sized,
+{
+    for segment in &mut path.segments {
+        match &mut segment.arguments {
+            PathArguments::None => {}
+            PathArguments::AngleBracketed(args) => walk_angle_bracketed(v, args),
+            PathArguments::Parenthesized(args) => {
+                v.visit_parenthesized_generic_arguments_mut(args);
+            }
+        }
+    }
+}
+
+pub(crate) fn walk_parenthesized_generic_arguments<V>(
+    v: &mut V,
+    args: &mut ParenthesizedGenericArguments,
+) where
+    V: VisitTypeMut + ?Sized,
+{
+    for input in &mut args.inputs {
+        v.visit_type_mut(input);
+    }
+    walk_return_type(v, &mut args.output);
+}
+
+pub(crate) fn walk_type_param_bound<V>(v: &mut V, bound: &mut TypeParamBound)
+where
+    V: VisitTypeMut + ?Sized,
+{
+    match bound {
+        TypeParamBound::Trait(bound) => v.visit_path_mut(&mut bound.path),
+        TypeParamBound::Lifetime(lifetime) => v.visit_lifetime_mut(lifetime),
+        _ => {}
+    }
+}
+
+pub(crate) fn walk_return_type<V>(v: &mut V, output: &mut ReturnType)
+where
+    V: VisitTypeMut + ?Sized,
+{
+    if let ReturnType::Type(_, ty) = output {
+        v.visit_type_mut(ty);
+    }
+}
+
+fn walk_angle_bracketed<V>(v: &mut V, args: &mut AngleBracketedGenericArguments)
+where
+    V: VisitTypeMut + ?Sized,
+{
+    for arg in &mut args.args {
+        match arg {
+            GenericArgument::Lifetime(lifetime) => v.visit_lifetime_mut(lifetime),
+            GenericArgument::Type(ty) => v.visit_type_mut(ty),
+            GenericArgument::AssocType(assoc) => {
+                if let Some(args) = &mut assoc.generics {
+                    walk_angle_bracketed(v, args);
+                }
+                v.visit_type_mut(&mut assoc.ty);
+            }
+            GenericArgument::Constraint(constraint) => {
+                if let Some(args) = &mut constraint.generics {
+                    walk_angle_bracketed(v, args);
+                }
+                for bound in &mut constraint.bounds {
+                    v.visit_type_param_bound_mut(bound);
+                }
+            }
+            _ => {}
+        }
+    }
+}
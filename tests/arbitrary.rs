@@ -0,0 +1,99 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Tests that the syntax trees generated by the Arbitrary impls print to tokens
+// that parse back to the same trees.
+
+#![cfg(all(feature = "arbitrary", feature = "parsing", feature = "extra-traits"))]
+
+use core::fmt::Debug;
+
+use arbitrary::{Arbitrary, Unstructured};
+use quote::ToTokens;
+use syn::parse::{Parse, ParseStream, Parser as _, Result};
+use syn_mid::{
+    Arm, Block, ExprMatch, FnArg, ItemFn, Local, Pat, PatIdent, PatOr, PatReference, PatRest,
+    PatStruct, PatTuple, PatTupleStruct, PatType, PatWild, Receiver, Signature, Stmt, StmtMacro,
+};
+
+const ITERATIONS: u64 = 500;
+
+/// Returns pseudo-random bytes for the `i`-th iteration.
+fn bytes(i: u64) -> Vec<u8> {
+    // xorshift64*
+    let mut state = (i + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    (0..1024)
+        .map(|_| {
+            state ^= state >> 12;
+            state ^= state << 25;
+            state ^= state >> 27;
+            (state.wrapping_mul(0x2545_F491_4F6C_DD1D) >> 56) as u8
+        })
+        .collect()
+}
+
+/// Generates trees of type `T`, wraps them with `wrap`, and checks that
+/// printing and parsing them with `parse` gives the same trees.
+fn roundtrip<T, U>(wrap: fn(T) -> U, parse: fn(ParseStream<'_>) -> Result<U>)
+where
+    T: for<'a> Arbitrary<'a>,
+    U: ToTokens + Debug + PartialEq,
+{
+    let mut generated = 0;
+    for i in 0..ITERATIONS {
+        let bytes = bytes(i);
+        let Ok(node) = T::arbitrary(&mut Unstructured::new(&bytes)) else { continue };
+        let node = wrap(node);
+        let tokens = node.to_token_stream();
+        match parse.parse2(tokens.clone()) {
+            Ok(parsed) => assert_eq!(parsed, node, "{tokens}"),
+            Err(e) => panic!("failed to parse `{tokens}`: {e}"),
+        }
+        generated += 1;
+    }
+    assert!(generated > ITERATIONS / 2, "generated {generated} trees");
+}
+
+fn parse<T: Parse>(input: ParseStream<'_>) -> Result<T> {
+    input.parse()
+}
+
+#[test]
+fn item_fn() {
+    roundtrip::<ItemFn, _>(|node| node, parse);
+    roundtrip::<Block, _>(|node| node, parse);
+    roundtrip::<Signature, _>(|node| node, parse);
+}
+
+#[test]
+fn fn_arg() {
+    roundtrip::<FnArg, _>(|node| node, parse);
+    roundtrip::<Receiver, _>(|node| node, parse);
+    roundtrip::<PatType, _>(FnArg::Typed, parse);
+}
+
+#[test]
+fn pat() {
+    let parse = Pat::parse_multi_with_leading_vert;
+    roundtrip::<Pat, _>(|node| node, parse);
+    roundtrip::<PatIdent, _>(Pat::Ident, parse);
+    roundtrip::<PatOr, _>(Pat::Or, parse);
+    roundtrip::<PatReference, _>(Pat::Reference, parse);
+    roundtrip::<PatRest, _>(Pat::Rest, parse);
+    roundtrip::<PatStruct, _>(Pat::Struct, parse);
+    roundtrip::<PatTuple, _>(Pat::Tuple, parse);
+    roundtrip::<PatTupleStruct, _>(Pat::TupleStruct, parse);
+    roundtrip::<PatWild, _>(Pat::Wild, parse);
+}
+
+#[test]
+fn expr_match() {
+    roundtrip::<ExprMatch, _>(|node| node, parse);
+    roundtrip::<Arm, _>(|node| node, parse);
+}
+
+#[test]
+fn stmt() {
+    roundtrip::<Stmt, _>(|node| node, parse);
+    roundtrip::<Local, _>(Stmt::Local, parse);
+    roundtrip::<StmtMacro, _>(Stmt::Macro, parse);
+}
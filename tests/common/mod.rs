@@ -0,0 +1,33 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use std::fmt::Write as _;
+
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+
+/// Prints `tokens` with a space between every two token trees, so that the
+/// spacing of punctuation does not matter.
+pub(crate) fn normalize(tokens: TokenStream) -> String {
+    let mut s = String::new();
+    for tt in tokens {
+        match tt {
+            TokenTree::Group(g) => {
+                let (open, close) = match g.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Brace => ("{", "}"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::None => ("", ""),
+                };
+                let _ = write!(s, "{open} {} {close} ", normalize(g.stream()));
+            }
+            tt => {
+                let _ = write!(s, "{tt} ");
+            }
+        }
+    }
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Parses `src` as tokens and prints them with [`normalize`].
+pub(crate) fn tokens(src: &str) -> String {
+    normalize(src.parse().unwrap())
+}
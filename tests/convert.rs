@@ -0,0 +1,96 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Tests for the conversions between the data structures of this crate and
+// syn.
+
+#![cfg(feature = "convert")]
+
+mod common;
+
+use quote::{ToTokens as _, quote};
+use syn::{AttrStyle, parse::Parser as _};
+use syn_mid::{Block, ItemFn, Pat, Signature};
+
+use self::common::{normalize, tokens};
+
+const ITEM_FN: &str = "#[inline] pub fn f<T>(&self, (a, b): (T, u8), S { x, .. }: S) -> T \
+                       { #![allow(unused)] let y = x; a }";
+
+#[test]
+fn item_fn_to_syn() {
+    let item: ItemFn = syn::parse_str(ITEM_FN).unwrap();
+    assert_eq!(item.block.inner_attrs.len(), 1);
+
+    let converted = syn::ItemFn::try_from(item).unwrap();
+    let styles: Vec<_> =
+        converted.attrs.iter().map(|attr| matches!(attr.style, AttrStyle::Inner(_))).collect();
+    assert_eq!(styles, [false, true]);
+    assert_eq!(converted.block.stmts.len(), 2);
+    assert_eq!(normalize(converted.to_token_stream()), tokens(ITEM_FN));
+}
+
+#[test]
+fn item_fn_from_syn() {
+    let item: syn::ItemFn = syn::parse_str(ITEM_FN).unwrap();
+    assert_eq!(item.attrs.len(), 2);
+
+    let converted = ItemFn::try_from(item).unwrap();
+    assert_eq!(converted.attrs.len(), 1);
+    assert_eq!(converted.block.inner_attrs.len(), 1);
+    assert_eq!(normalize(converted.to_token_stream()), tokens(ITEM_FN));
+}
+
+#[test]
+fn signature() {
+    let src = "unsafe extern \"C\" fn f<'a>(self: &'a Self, (a, _): (u8, u8), ...) -> &'a u8";
+    let sig: Signature = syn::parse_str(src).unwrap();
+    let converted = syn::Signature::from(sig);
+    assert_eq!(normalize(converted.to_token_stream()), tokens(src));
+    let sig = Signature::try_from(converted).unwrap();
+    assert_eq!(normalize(sig.to_token_stream()), tokens(src));
+}
+
+#[test]
+fn block_inner_attrs() {
+    let block: Block = syn::parse_str("{ #![allow(unused)] let x = 1; }").unwrap();
+    let Err(e) = syn::Block::try_from(block) else { panic!("inner attributes were converted") };
+    assert_eq!(e.to_string(), "inner attributes cannot be converted to syn::Block");
+
+    let block: Block = syn::parse_str("{ let x = 1; x }").unwrap();
+    let converted = syn::Block::try_from(block).unwrap();
+    assert_eq!(converted.stmts.len(), 2);
+    assert_eq!(normalize(Block::from(converted).to_token_stream()), tokens("{ let x = 1; x }"));
+}
+
+fn pat_from_syn(src: &str) -> syn::Result<Pat> {
+    Pat::try_from(syn::Pat::parse_multi_with_leading_vert.parse_str(src).unwrap())
+}
+
+#[test]
+fn pat_from_syn_errors() {
+    for (src, msg) in [
+        ("x @ Some(_)", "unsupported pattern: subpattern binding"),
+        ("(a, ref b @ 1)", "unsupported pattern: subpattern binding"),
+        ("S { x: y @ _, .. }", "unsupported pattern: subpattern binding"),
+        ("<T as Trait>::Assoc { .. }", "unsupported pattern: qualified path"),
+        ("&<T as Trait>::Assoc(x)", "unsupported pattern: qualified path"),
+        ("[a, b]", "unsupported pattern"),
+        ("A | 0..=9", "unsupported pattern"),
+    ] {
+        let Err(e) = pat_from_syn(src) else { panic!("`{src}` was converted") };
+        assert_eq!(e.to_string(), msg, "{src}");
+    }
+
+    let pat = pat_from_syn("(a, &mut b, S { x, .. }, T(_, ..))").unwrap();
+    assert_eq!(normalize(pat.to_token_stream()), tokens("(a, &mut b, S { x, .. }, T(_, ..))"));
+}
+
+#[test]
+fn pat_verbatim() {
+    let pat = Pat::Verbatim(quote!(box x));
+    let converted = syn::Pat::from(pat);
+    assert!(matches!(&converted, syn::Pat::Verbatim(tokens) if tokens.to_string() == "box x"));
+
+    let pat = Pat::try_from(converted).unwrap();
+    assert!(matches!(&pat, Pat::Verbatim(tokens) if tokens.to_string() == "box x"));
+}
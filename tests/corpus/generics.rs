@@ -0,0 +1,58 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Generics, return types, and qualifiers.
+
+pub fn lifetimes<'a, 'b: 'a>(x: &'a u8, y: &'b u8) -> &'a u8 {
+    x
+}
+
+pub fn types<T, U: Clone + 'static, V: ?Sized>(t: T, u: U, v: &V) {}
+
+pub fn defaults_and_bounds<T>(t: T) -> impl Iterator<Item = T>
+where
+    T: Clone + Iterator<Item = T>,
+    for<'a> &'a T: IntoIterator,
+{
+    t
+}
+
+pub fn consts<const N: usize, const M: bool>(x: [u8; N]) -> [u8; { N * 2 }] {
+    todo!()
+}
+
+pub fn impl_trait(f: impl Fn(u8) -> u8, g: impl FnOnce() -> Box<dyn Fn() -> u8>) -> impl Fn() {
+    || {}
+}
+
+pub fn never() -> ! {
+    loop {}
+}
+
+pub fn fn_pointer(f: fn(u8) -> u8, g: unsafe extern "C" fn(*const u8, ...)) {}
+
+pub async fn asyncness<T: Send>(x: T) -> T {
+    x.await
+}
+
+pub const unsafe fn qualifiers() {}
+
+pub const async unsafe extern "C" fn all_qualifiers() {}
+
+pub(super) fn vis_super() {}
+
+pub(in crate::a) fn vis_in() {}
+
+pub fn qself<T: Iterator>(x: <T as Iterator>::Item) -> <T as IntoIterator>::IntoIter {
+    todo!()
+}
+
+#[inline]
+#[must_use = "reason"]
+pub fn attrs() -> u8 {
+    #![allow(clippy::all)]
+    0
+}
+
+pub fn raw_ident(r#fn: u8, r#match: u8) -> u8 {
+    r#fn + r#match
+}
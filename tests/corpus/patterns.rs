@@ -0,0 +1,94 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Patterns in function arguments, `let` statements, and `match` arms.
+
+fn ident(a: u8, mut b: u8, ref c: u8, ref mut d: u8, r#type: u8) {}
+
+fn wild(_: u8, _x: u8) {}
+
+fn tuple((a, b): (u8, u8), (c,): (u8,), (): (), (d, ..): (u8, u8, u8), (.., e): (u8, u8)) {}
+
+fn tuple_struct(Foo(a, b): Foo, Bar(..): Bar, Baz(a, .., b,): Baz, Qux(): Qux) {}
+
+fn struct_(
+    Foo { a, b }: Foo,
+    Bar { a: x, ref b, ref mut c, .. }: Bar,
+    Baz { 0: x, 1: (y, z), }: Baz,
+    Qux {}: Qux,
+    Quux { .. }: Quux,
+) {
+}
+
+fn path(self::Foo { a }: self::Foo, crate::Bar(b): crate::Bar, super::baz::Baz: Baz) {}
+
+fn reference(&a: &u8, &mut b: &mut u8, &(c, d): &(u8, u8), &&e: &&u8) {}
+
+fn nested(Foo((a, Bar { b, .. }), &_): Foo, (Ok(x) | Err(x),): (Result<u8, u8>,)) {}
+
+fn let_patterns() {
+    let a;
+    let b = 1;
+    let (c, d) = (1, 2);
+    let Foo { x, y: (z, w), .. } = foo;
+    let Some(e) = f else { return };
+    let (Ok(g) | Err(g)) = h;
+    let ref mut i: u8 = 0;
+    let (n,): (u8,) = (1,);
+    let -1 = o else { unreachable!() };
+    let Foo::BAR = p else { panic!() };
+}
+
+fn match_patterns(x: Option<u8>) {
+    match x {
+        Some(0) => {}
+        Some(1 | 2) => {}
+        Some(3..=5) => {}
+        Some(n @ 6..) if n % 2 == 0 => {}
+        | None | Some(_) => {}
+    }
+    match (x, x) {
+        (Some(a), Some(b)) if a == b => a,
+        (Some(a), _) | (_, Some(a)) => a,
+        (None, None) => 0,
+    };
+    match s {
+        "a" | "b" => 'c',
+        _ if true => 'd',
+        _ => unreachable!(),
+    }
+}
+
+fn range_and_slice_patterns(x: u8, c: char, s: &[u8], p: Foo) {
+    match x {
+        0..=9 => {}
+        10..20 if x % 2 == 0 => {}
+        ..=30 => {}
+        b'a'..=b'z' | b'A'..=b'Z' => {}
+        u8::MIN..=MAX => {}
+        n @ 200.. => {}
+        _ => {}
+    }
+    match c {
+        'a'..='z' => {}
+        '0'..='9' | '_' => {}
+        _ => {}
+    }
+    match s {
+        [] => {}
+        [a] => {}
+        [a, .., b] => {}
+        [first, rest @ ..] => {}
+        [.., 0] | [0, ..] => {}
+        &[ref a, ref mut b] => {}
+        [_, [x, y], _] => {}
+    }
+    match p {
+        Foo { a: 0..=9, b: [x, ..] } => {}
+        Foo::<u8> { .. } => {}
+        <T as Trait>::C => {}
+        m!() => {}
+    }
+    let [a, b, c] = arr;
+    let [x, ..] = s else { return };
+    let (0..=9 | 20..) = x else { return };
+}
@@ -0,0 +1,53 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// The `self` argument and attributes on function arguments.
+
+struct Foo;
+
+impl Foo {
+    fn by_value(self) {}
+    fn by_mut_value(mut self) {}
+    fn by_ref(&self) {}
+    fn by_mut_ref(&mut self) -> &mut Self {
+        self
+    }
+    fn with_lifetime<'a>(&'a self, x: &'a u8) -> &'a u8 {
+        x
+    }
+    fn with_mut_lifetime<'a>(&'a mut self) {}
+    fn typed(self: Self) {}
+    fn typed_mut(mut self: Self) {}
+    fn boxed(self: Box<Self>) {}
+    fn pinned(self: Pin<&mut Self>) {}
+    fn rc(self: Rc<Self>, other: Rc<Self>) {}
+    fn explicit_ref(self: &Self) {}
+    fn explicit_mut_ref(self: &'static mut Self) {}
+    fn generic<T: Deref<Target = Self>>(self: T) {}
+    pub(crate) fn vis(&self) {}
+    pub async fn asyncness(&self) {}
+    const fn constness(&self) {}
+    unsafe fn unsafety(&self) {}
+}
+
+trait Bar {
+    fn required(&self);
+    fn provided(&self) -> u8 {
+        0
+    }
+    fn provided_mut(&mut self, #[cfg(test)] x: u8) {}
+}
+
+fn param_attrs(
+    #[cfg(something)] a: u8,
+    #[allow(unused)] #[cfg_attr(feature = "x", deny(warnings))] b: u8,
+    #[doc = "c"] (c, d): (u8, u8),
+) {
+}
+
+impl Foo {
+    fn receiver_attrs(#[cfg(something)] self, #[allow(unused)] x: u8) {}
+    fn receiver_ref_attrs(#[must_use] &self) {}
+    fn receiver_typed_attrs(#[allow(unused)] mut self: Box<Self>) {}
+}
+
+fn trailing_comma(a: u8, b: u8,) {}
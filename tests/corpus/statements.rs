@@ -0,0 +1,95 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Statements whose expressions are not parsed.
+
+fn locals() {
+    let a = 1;
+    let b: Vec<u8> = Vec::new();
+    let c = if a == 1 { 2 } else { 3 };
+    let d = match a { 1 => 2, _ => 3 };
+    let Some(e) = f() else { return };
+    let Some(g) = (if true { h } else { i }) else { panic!() };
+    let j = loop { break 1 };
+    let k = { 1 } + { 2 };
+    let l = || -> u8 { 1 };
+    let m = async move { 1 };
+    let n = unsafe { core::mem::zeroed::<u8>() };
+    let o = const { 1 };
+    let p = Foo { a: 1, b: 2 };
+    let q = 'label: { break 'label 1 };
+}
+
+fn exprs() {
+    a();
+    b.c()?;
+    if a { b } else { c }
+    if let Some(x) = y { z } else if w { v }
+    match a {}
+    match a { _ => {} }.method();
+    loop {}
+    while a {}
+    while let Some(x) = iter.next() {}
+    for x in iter {}
+    for (a, b) in (Foo { a: 1 }).iter() {}
+    'outer: loop { break 'outer; }
+    unsafe { a() }
+    unsafe { a() }.b();
+    {}
+    { a }?;
+    ;;
+    return;
+}
+
+fn macros() {
+    println!("{}", 1);
+    vec![1, 2, 3];
+    thread_local! { static A: u8 = 0; }
+    macro_rules! foo { () => {}; }
+    foo! {}
+    foo! {};
+    foo!();
+    a::b!(c);
+    try_something! { x }
+    m! {}.n();
+    m!() + 1
+}
+
+fn items() {
+    fn nested() {}
+    fn nested_generic<const N: usize>(x: [u8; N]) -> Foo<{ N }> {
+        todo!()
+    }
+    pub(crate) async unsafe fn nested_qualifiers() {}
+    const fn nested_const() -> u8 { 0 }
+    const A: u8 = 0;
+    static B: u8 = 0;
+    static mut C: u8 = 0;
+    struct D;
+    struct E { a: u8 }
+    struct F<T = Box<dyn Fn() -> u8>>(T);
+    enum G { A, B { c: u8 } }
+    type H = u8;
+    use std::mem;
+    impl D { fn f(&self) {} }
+    trait I { fn g(&self); }
+    mod j {}
+    extern "C" { fn k(); }
+    extern crate alloc;
+    union L { a: u8 }
+    #[derive(Debug)]
+    struct M;
+    unsafe impl Send for D {}
+    const _: () = {};
+    const N: bool = 1 < 2;
+    const O: bool = 2 > 1;
+    static P: u8 = 1 << 2;
+    static mut Q: bool = a < b && c > d;
+    const R: Foo<u8> = Foo::<u8>::new(1 < 2);
+}
+
+fn trailing_expr() -> u8 {
+    #[allow(unused)]
+    let a = 1;
+    #[rustfmt::skip]
+    a
+}
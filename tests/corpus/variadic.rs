@@ -0,0 +1,25 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// C-variadic functions.
+
+#![feature(c_variadic)]
+
+pub unsafe extern "C" fn anonymous(n: usize, ...) {}
+
+pub unsafe extern "C" fn named(n: usize, mut args: ...) -> usize {
+    args.arg::<usize>()
+}
+
+pub unsafe extern "C" fn trailing_comma(n: usize, args: ..., ) {}
+
+pub unsafe extern "C" fn attrs(#[cfg(something)] n: usize, #[allow(unused)] _: ...) {}
+
+pub unsafe extern "C" fn pattern(n: usize, _: ...) {}
+
+extern "C" fn abi() {}
+
+extern fn no_abi_name() {}
+
+pub unsafe extern "system" fn system(a: u32) -> u32 {
+    a
+}
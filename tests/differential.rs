@@ -0,0 +1,395 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Differential tests against the parsers of syn with "full" feature.
+//
+// The parsers of this crate are copied from syn 2.0.37 and can drift from syn.
+// These tests parse the same inputs with both, and check that they accept and
+// reject the same inputs and that the accepted inputs print the same tokens.
+//
+// The inputs are the ones listed below and the signatures, arguments, and
+// patterns in the hand-written files in tests/corpus. The inputs for which the parsers are known to
+// differ are listed in the `EXPECTED_*` constants, and a test fails if one of
+// them no longer differs, so that the lists stay accurate.
+
+#![cfg(all(feature = "parsing", feature = "printing"))]
+
+use std::{collections::BTreeSet, fmt::Write as _, fs, path::Path};
+
+use quote::ToTokens;
+use syn::{
+    parse::{Parse as _, ParseStream, Parser as _, Result},
+    visit::{self, Visit},
+};
+
+const PATS: &[&str] = &[
+    // Accepted by both.
+    "x",
+    "mut x",
+    "ref x",
+    "ref mut x",
+    "r#type",
+    "self",
+    "_",
+    "0",
+    "-1",
+    "'a'",
+    "\"s\"",
+    "b'a'",
+    "1.0",
+    "true",
+    "Foo",
+    "Foo::Bar",
+    "::Foo",
+    "crate::Foo",
+    "self::Foo",
+    "super::Foo",
+    "Self::Foo",
+    "Foo::<u8>::Bar",
+    "Foo()",
+    "Foo(a, b)",
+    "Foo(a, ..)",
+    "Foo(a, b,)",
+    "Foo::Bar(a)",
+    "Foo {}",
+    "Foo { a }",
+    "Foo { a, b: c }",
+    "Foo { ref a, ref mut b, mut c }",
+    "Foo { 0: a, 1: b }",
+    "Foo { a, .. }",
+    "Foo { .. }",
+    "Foo { #[cfg(x)] a, #[cfg(y)] .. }",
+    "Self { a }",
+    "Self(a)",
+    "()",
+    "(a)",
+    "(a,)",
+    "(a, b)",
+    "(a, ..)",
+    "(.., a)",
+    "(..)",
+    "&a",
+    "&mut a",
+    "&&a",
+    "&(a, b)",
+    "..",
+    "a | b",
+    "| a | b",
+    "(a | b)",
+    "(a | b, c)",
+    "Foo(a | b)",
+    "Foo { a: b | c }",
+    // Rejected by both.
+    "",
+    "1 +",
+    "Foo {",
+    "Foo { a b }",
+    "Foo(a b)",
+    "a b",
+    "&",
+    "ref",
+    "mut",
+    "ref mut",
+    "||",
+    "Foo { .., a }",
+    // Not supported by this crate.
+    "[a, b]",
+    "[a, .., b]",
+    "&[a]",
+    "0..=9",
+    "0..",
+    "..=9",
+    "'a'..='z'",
+    "x @ Some(_)",
+    "ref x @ 1",
+    "box x",
+    "m!()",
+    "m![]",
+    "m! {}",
+    "<T as Trait>::C",
+    "<T>::C",
+    "const { 1 }",
+    "Foo { box a }",
+    "Foo(&[a])",
+];
+
+const EXPECTED_PATS: &[(&str, &str)] = &[
+    ("[a, b]", "slice patterns are not supported"),
+    ("[a, .., b]", "slice patterns are not supported"),
+    ("&[a]", "slice patterns are not supported"),
+    ("Foo(&[a])", "slice patterns are not supported"),
+    ("0..=9", "range patterns are not supported"),
+    ("0..", "range patterns are not supported"),
+    ("..=9", "range patterns are not supported"),
+    ("'a'..='z'", "range patterns are not supported"),
+    ("x @ Some(_)", "subpatterns of identifier patterns are not supported"),
+    ("ref x @ 1", "subpatterns of identifier patterns are not supported"),
+    ("box x", "box patterns are not supported"),
+    ("Foo { box a }", "box patterns are not supported"),
+    ("m!()", "macro patterns are not supported"),
+    ("m![]", "macro patterns are not supported"),
+    ("m! {}", "macro patterns are not supported"),
+    ("<T as Trait>::C", "qualified paths are not supported"),
+    ("<T>::C", "qualified paths are not supported"),
+    ("const { 1 }", "const block patterns are not supported"),
+    // From tests/corpus.
+    ("3 ..= 5", "range patterns are not supported"),
+    ("6 ..", "range patterns are not supported"),
+    ("Some (3 ..= 5)", "range patterns are not supported"),
+    ("Some (n @ 6 ..)", "subpatterns of identifier patterns are not supported"),
+    ("n @ 6 ..", "subpatterns of identifier patterns are not supported"),
+    ("0 ..= 9", "range patterns are not supported"),
+    ("10 .. 20", "range patterns are not supported"),
+    ("..= 30", "range patterns are not supported"),
+    ("20 ..", "range patterns are not supported"),
+    ("(0 ..= 9 | 20 ..)", "range patterns are not supported"),
+    ("b'a' ..= b'z'", "range patterns are not supported"),
+    ("b'A' ..= b'Z'", "range patterns are not supported"),
+    ("u8 :: MIN ..= MAX", "range patterns are not supported"),
+    ("'a' ..= 'z'", "range patterns are not supported"),
+    ("'0' ..= '9'", "range patterns are not supported"),
+    ("200 ..", "range patterns are not supported"),
+    ("n @ 200 ..", "subpatterns of identifier patterns are not supported"),
+    ("Foo { a : 0 ..= 9 , b : [x , ..] }", "range patterns are not supported"),
+    ("[]", "slice patterns are not supported"),
+    ("[a]", "slice patterns are not supported"),
+    ("[a , .. , b]", "slice patterns are not supported"),
+    ("[a , b , c]", "slice patterns are not supported"),
+    ("[first , rest @ ..]", "slice patterns are not supported"),
+    ("rest @ ..", "subpatterns of identifier patterns are not supported"),
+    ("[.. , 0]", "slice patterns are not supported"),
+    ("[0 , ..]", "slice patterns are not supported"),
+    ("[x , ..]", "slice patterns are not supported"),
+    ("[x , y]", "slice patterns are not supported"),
+    ("[_ , [x , y] , _]", "slice patterns are not supported"),
+    ("[ref a , ref mut b]", "slice patterns are not supported"),
+    ("& [ref a , ref mut b]", "slice patterns are not supported"),
+    ("< T as Trait > :: C", "qualified paths are not supported"),
+    ("m ! ()", "macro patterns are not supported"),
+];
+
+/// The inputs for which `Pat::parse_multi` and
+/// `Pat::parse_multi_with_leading_vert` are known to differ, in addition to
+/// `EXPECTED_PATS`. `Pat::parse_single` of both crates rejects them.
+const EXPECTED_MULTI_PATS: &[(&str, &str)] = &[
+    // From tests/corpus.
+    ("0 ..= 9 | 20 ..", "range patterns are not supported"),
+    ("'0' ..= '9' | '_'", "range patterns are not supported"),
+    ("b'a' ..= b'z' | b'A' ..= b'Z'", "range patterns are not supported"),
+    ("[.. , 0] | [0 , ..]", "slice patterns are not supported"),
+];
+
+const FN_ARGS: &[&str] = &[
+    // Accepted by both.
+    "self",
+    "mut self",
+    "&self",
+    "&mut self",
+    "&'a self",
+    "&'a mut self",
+    "self: Self",
+    "mut self: Self",
+    "self: Box<Self>",
+    "self: &Self",
+    "self: &'a mut Self",
+    "self: Pin<&mut Self>",
+    "#[cfg(x)] self",
+    "#[cfg(x)] &self",
+    "x: u8",
+    "mut x: u8",
+    "_: u8",
+    "(a, b): (u8, u8)",
+    "Foo { a, .. }: Foo",
+    "&x: &u8",
+    "#[cfg(x)] x: u8",
+    "#[cfg(x)] #[cfg(y)] x: u8",
+    "x: impl Fn() -> u8",
+    "x: &dyn Trait",
+    "x: [u8; N]",
+    "x: fn(u8) -> u8",
+    "x: <T as Trait>::Assoc",
+    // Rejected by both.
+    "",
+    "x",
+    "x:",
+    ": u8",
+    "&self: Self",
+    "self: ",
+    "x: u8,",
+    "...",
+    "x: ...",
+    "a | b: u8",
+    // Not supported by this crate.
+    "[a, b]: [u8; 2]",
+];
+
+const EXPECTED_FN_ARGS: &[(&str, &str)] =
+    &[("[a, b]: [u8; 2]", "slice patterns are not supported")];
+
+const SIGNATURES: &[&str] = &[
+    // Accepted by both.
+    "fn f()",
+    "fn f() -> u8",
+    "fn f<T>(x: T) -> T",
+    "fn f<'a, T: 'a>(x: &'a T) where T: Clone",
+    "fn f<const N: usize>(x: [u8; N])",
+    "const fn f()",
+    "async fn f()",
+    "unsafe fn f()",
+    "extern fn f()",
+    "extern \"C\" fn f()",
+    "const async unsafe extern \"C\" fn f()",
+    "fn f(self)",
+    "fn f(&self, x: u8)",
+    "fn f(&mut self, x: u8,)",
+    "fn f(x: u8, y: u8)",
+    "fn f(#[cfg(x)] self, #[cfg(y)] x: u8)",
+    "unsafe extern \"C\" fn f(x: u8, ...)",
+    "unsafe extern \"C\" fn f(x: u8, ...,)",
+    "unsafe extern \"C\" fn f(x: u8, args: ...)",
+    "unsafe extern \"C\" fn f(x: u8, #[cfg(x)] mut args: ...)",
+    "unsafe extern \"C\" fn f(...)",
+    "fn f(x: u8) -> impl Iterator<Item = u8>",
+    "fn f() -> !",
+    "fn r#try()",
+    "fn f() where",
+    // Rejected by both.
+    "",
+    "f()",
+    "fn ()",
+    "fn f",
+    "fn f() ->",
+    "fn f(x: u8, self)",
+    "fn f(self, self)",
+    "fn f(&self, &mut self)",
+    "fn f(..., x: u8)",
+    "fn f(x: u8, ..., y: u8)",
+    "fn f(x: u8 y: u8)",
+    "async const fn f()",
+    // Not supported by this crate.
+    "fn f([a, b]: [u8; 2])",
+];
+
+const EXPECTED_SIGNATURES: &[(&str, &str)] =
+    &[("fn f([a, b]: [u8; 2])", "slice patterns are not supported")];
+
+fn differs<A: ToTokens, B: ToTokens>(
+    input: &str,
+    syn: fn(ParseStream<'_>) -> Result<A>,
+    syn_mid: fn(ParseStream<'_>) -> Result<B>,
+) -> Option<String> {
+    match (syn.parse_str(input), syn_mid.parse_str(input)) {
+        (Ok(a), Ok(b)) => {
+            let (a, b) = (a.to_token_stream().to_string(), b.to_token_stream().to_string());
+            (a != b).then(|| format!("syn prints `{a}`, syn-mid prints `{b}`"))
+        }
+        (Err(_), Err(_)) => None,
+        (Ok(_), Err(e)) => Some(format!("syn accepts, syn-mid rejects: {e}")),
+        (Err(e), Ok(_)) => Some(format!("syn rejects: {e}, syn-mid accepts")),
+    }
+}
+
+fn check<A: ToTokens, B: ToTokens>(
+    inputs: &BTreeSet<String>,
+    expected: &[(&str, &str)],
+    syn: fn(ParseStream<'_>) -> Result<A>,
+    syn_mid: fn(ParseStream<'_>) -> Result<B>,
+) {
+    let mut failures = String::new();
+    for input in inputs {
+        let is_expected = expected.iter().any(|&(e, _)| e == input);
+        match differs(input, syn, syn_mid) {
+            Some(diff) if !is_expected => {
+                let _ = writeln!(failures, "unexpected difference for `{input}`: {diff}");
+            }
+            None if is_expected => {
+                let _ = writeln!(failures, "expected difference for `{input}` no longer occurs");
+            }
+            _ => {}
+        }
+    }
+    for &(input, _) in expected {
+        if !inputs.contains(input) {
+            let _ = writeln!(failures, "expected difference for `{input}` is not tested");
+        }
+    }
+    assert!(failures.is_empty(), "{failures}");
+}
+
+#[derive(Default)]
+struct Corpus {
+    sigs: BTreeSet<String>,
+    fn_args: BTreeSet<String>,
+    pats: BTreeSet<String>,
+}
+
+impl<'ast> Visit<'ast> for Corpus {
+    fn visit_signature(&mut self, node: &'ast syn::Signature) {
+        self.sigs.insert(node.to_token_stream().to_string());
+        visit::visit_signature(self, node);
+    }
+
+    fn visit_fn_arg(&mut self, node: &'ast syn::FnArg) {
+        self.fn_args.insert(node.to_token_stream().to_string());
+        visit::visit_fn_arg(self, node);
+    }
+
+    fn visit_pat(&mut self, node: &'ast syn::Pat) {
+        self.pats.insert(node.to_token_stream().to_string());
+        visit::visit_pat(self, node);
+    }
+}
+
+fn corpus() -> Corpus {
+    let mut corpus = Corpus::default();
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/corpus");
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if !path.extension().is_some_and(|ext| ext == "rs") {
+            continue;
+        }
+        let src = fs::read_to_string(path).unwrap();
+        corpus.visit_file(&syn::parse_file(&src).unwrap());
+    }
+    corpus
+}
+
+fn inputs(listed: &[&str], corpus: BTreeSet<String>) -> BTreeSet<String> {
+    listed.iter().map(|&s| s.to_owned()).chain(corpus).collect()
+}
+
+#[test]
+fn pat_parse_single() {
+    let inputs = inputs(PATS, corpus().pats);
+    check(&inputs, EXPECTED_PATS, syn::Pat::parse_single, syn_mid::Pat::parse_single);
+}
+
+#[test]
+fn pat_parse_multi() {
+    let inputs = inputs(PATS, corpus().pats);
+    let expected = [EXPECTED_PATS, EXPECTED_MULTI_PATS].concat();
+    check(&inputs, &expected, syn::Pat::parse_multi, syn_mid::Pat::parse_multi);
+}
+
+#[test]
+fn pat_parse_multi_with_leading_vert() {
+    let inputs = inputs(PATS, corpus().pats);
+    let expected = [EXPECTED_PATS, EXPECTED_MULTI_PATS].concat();
+    check(
+        &inputs,
+        &expected,
+        syn::Pat::parse_multi_with_leading_vert,
+        syn_mid::Pat::parse_multi_with_leading_vert,
+    );
+}
+
+#[test]
+fn fn_arg() {
+    let inputs = inputs(FN_ARGS, corpus().fn_args);
+    check(&inputs, EXPECTED_FN_ARGS, syn::FnArg::parse, syn_mid::FnArg::parse);
+}
+
+#[test]
+fn signature() {
+    let inputs = inputs(SIGNATURES, corpus().sigs);
+    check(&inputs, EXPECTED_SIGNATURES, syn::Signature::parse, syn_mid::Signature::parse);
+}
@@ -0,0 +1,116 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Tests for finding and parsing `match` expressions in a block.
+
+#![cfg(all(feature = "parsing", feature = "printing"))]
+
+use proc_macro2::TokenStream;
+use quote::ToTokens as _;
+use syn_mid::{Block, ExprMatch, Pat};
+
+fn tokens(src: &str) -> String {
+    src.parse::<TokenStream>().unwrap().to_string()
+}
+
+fn matches(src: &str) -> Vec<ExprMatch> {
+    let block: Block = syn::parse_str(&format!("{{ {src} }}")).unwrap();
+    block.top_level_matches().unwrap()
+}
+
+#[test]
+fn supported_patterns() {
+    let src = "match x { Some(0) | None => {} Foo { a, .. } if a > 0 => a, (_, ref b) => b }";
+    let m = matches(src);
+    assert_eq!(m.len(), 1);
+    let pats: Vec<_> = m[0].arms.iter().map(|arm| &arm.pat).collect();
+    assert!(matches!(pats[0], Pat::Or(_)));
+    assert!(matches!(pats[1], Pat::Struct(_)));
+    assert!(matches!(pats[2], Pat::Tuple(_)));
+    assert_eq!(m[0].to_token_stream().to_string(), tokens(src));
+}
+
+#[test]
+fn unsupported_patterns() {
+    let src = "match x {
+        0..=10 => { return true; }
+        'a'..='z' | 'A'..='Z' => 1,
+        [a, ..] if a > 0 => a,
+        n @ 11.. => n,
+        Foo::Bar(1..=5) => 2,
+        _ => 3,
+    }";
+    let m = matches(src);
+    assert_eq!(m.len(), 1);
+    let arms = &m[0].arms;
+    assert_eq!(arms.len(), 6);
+    let verbatim: Vec<_> = arms[..5]
+        .iter()
+        .map(|arm| match &arm.pat {
+            Pat::Verbatim(tokens) => tokens.to_string(),
+            pat => panic!("expected verbatim, found `{}`", pat.to_token_stream()),
+        })
+        .collect();
+    assert_eq!(
+        verbatim,
+        [
+            tokens("0..=10"),
+            tokens("'a'..='z' | 'A'..='Z'"),
+            tokens("[a, ..]"),
+            tokens("n @ 11.."),
+            tokens("Foo::Bar(1..=5)"),
+        ]
+    );
+    assert_eq!(arms[2].guard.as_ref().unwrap().1.to_string(), tokens("a > 0"));
+    assert!(matches!(arms[5].pat, Pat::Wild(_)));
+    assert_eq!(m[0].to_token_stream().to_string(), tokens(src));
+}
+
+#[test]
+fn nested_matches() {
+    let m =
+        matches("let a = match x { 0..=9 => match y { [b] => b, _ => 0 }, _ => 1 }; match z {}");
+    assert_eq!(m.len(), 2);
+    assert_eq!(m[0].arms.len(), 2);
+    assert!(m[1].arms.is_empty());
+}
+
+fn bodies(src: &str) -> Vec<String> {
+    let m = matches(src);
+    assert_eq!(m.len(), 1);
+    assert_eq!(m[0].to_token_stream().to_string(), tokens(src));
+    m[0].arms.iter().map(|arm| arm.body.to_string()).collect()
+}
+
+#[test]
+fn closure_bodies() {
+    assert_eq!(bodies("match x { _ => |a, b| a + b }"), [tokens("|a, b| a + b")]);
+    assert_eq!(
+        bodies(
+            "match x { A => move |a: (u8, u8), b: Vec<A, B>| -> Foo<A, B> { f(a, b) }, B => || 1, _ => |a| a | 1 }"
+        ),
+        [
+            tokens("move |a: (u8, u8), b: Vec<A, B>| -> Foo<A, B> { f(a, b) }"),
+            tokens("|| 1"),
+            tokens("|a| a | 1"),
+        ]
+    );
+    assert_eq!(bodies("match x { A => a || b, _ => a | b }"), [tokens("a || b"), tokens("a | b")]);
+}
+
+#[test]
+fn generic_argument_bodies() {
+    assert_eq!(
+        bodies("match x { A => a as Vec<B, C>, _ => 1 }"),
+        [tokens("a as Vec<B, C>"), tokens("1")]
+    );
+    assert_eq!(
+        bodies(
+            "match x { A => a as *const m::T<B, C> as usize, B => a as u8 + 1, _ => f::<A, B>() }"
+        ),
+        [tokens("a as *const m::T<B, C> as usize"), tokens("a as u8 + 1"), tokens("f::<A, B>()")]
+    );
+    assert_eq!(
+        bodies("match x { A => 1 + <T as Tr<A, B>>::f(), B => a < b, _ => a << b }"),
+        [tokens("1 + <T as Tr<A, B>>::f()"), tokens("a < b"), tokens("a << b")]
+    );
+}
@@ -0,0 +1,74 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Tests for the `PartialEq` and `Hash` impls of the data structures that
+// contain unparsed tokens.
+
+#![cfg(all(feature = "extra-traits", feature = "parsing", feature = "printing"))]
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher as _},
+};
+
+use quote::quote;
+use syn::parse::{Parse, ParseStream, Parser as _, Result};
+use syn_mid::{Block, Pat, Stmt};
+
+fn hash<T: Hash>(node: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    node.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Checks that the trees parsed from `a` and `b` are equal and hash the same,
+/// and that the tree parsed from `other` is not equal to them.
+fn check<T>(parse: fn(ParseStream<'_>) -> Result<T>, a: &str, b: &str, other: &str)
+where
+    T: Eq + Hash + std::fmt::Debug,
+{
+    let a = parse.parse_str(a).unwrap();
+    let b = parse.parse_str(b).unwrap();
+    let other = parse.parse_str(other).unwrap();
+    assert_eq!(a, b);
+    assert_eq!(hash(&a), hash(&b));
+    assert_ne!(a, other);
+}
+
+fn parse<T: Parse>(input: ParseStream<'_>) -> Result<T> {
+    input.parse()
+}
+
+#[test]
+fn block() {
+    check::<Block>(
+        parse,
+        "{ #![allow(unused)] let x=f(a,b); x }",
+        "{\n    #![allow(unused)]\n    let x = f( a , b );\n    x\n}",
+        "{ #![allow(unused)] let x = f(a, c); x }",
+    );
+    check::<Block>(parse, "{ x }", "{x}", "{ #![allow(unused)] x }");
+}
+
+#[test]
+fn stmt() {
+    check::<Stmt>(parse, "let x:u8=f(a,b);", "let x : u8 = f( a, b ) ;", "let x: u8 = f(a);");
+    check::<Stmt>(parse, "f(a,b);", "f( a , b );", "f(b, a);");
+    check::<Stmt>(parse, "struct S{a:u8}", "struct S {\n    a: u8\n}", "struct S { a: u16 }");
+    check::<Stmt>(parse, "m!{a b}", "m! { a  b }", "m! { a }");
+}
+
+#[test]
+fn pat() {
+    check::<Pat>(
+        Pat::parse_multi_with_leading_vert,
+        "S{a,b:(c,_),..}|T(d)",
+        "S { a, b: (c, _), .. } | T(d)",
+        "S { a, b: (c, _), .. } | T(e)",
+    );
+
+    let a = Pat::Verbatim(quote!(box x));
+    let b = Pat::Verbatim("box\n  x".parse().unwrap());
+    assert_eq!(a, b);
+    assert_eq!(hash(&a), hash(&b));
+    assert_ne!(a, Pat::Verbatim(quote!(box y)));
+}
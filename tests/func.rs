@@ -0,0 +1,365 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Tests for the accessors and transformations of function signatures.
+
+#![cfg(all(feature = "parsing", feature = "printing"))]
+
+mod common;
+
+use quote::{ToTokens as _, quote};
+use syn_mid::{ItemFn, Signature};
+
+use self::common::{normalize, tokens};
+
+fn sig(src: &str) -> Signature {
+    syn::parse_str(src).unwrap()
+}
+
+#[cfg(feature = "clone-impls")]
+#[test]
+fn output_type() {
+    let s = sig("fn f()");
+    assert_eq!(normalize(s.output_type().to_token_stream()), tokens("()"));
+    assert_eq!(normalize(s.to_token_stream()), tokens("fn f()"));
+    let ty = sig("fn f() -> Option<u8>").output_type().to_token_stream();
+    assert_eq!(normalize(ty), tokens("Option<u8>"));
+}
+
+#[test]
+fn output_type_mut() {
+    let mut s = sig("fn f()");
+    let _ = s.output_type_mut();
+    assert_eq!(normalize(s.to_token_stream()), tokens("fn f() -> ()"));
+
+    let mut s = sig("fn f(&self)");
+    *s.output_type_mut() = syn::parse_quote!(u8);
+    assert_eq!(normalize(s.to_token_stream()), tokens("fn f(&self) -> u8"));
+}
+
+#[test]
+fn receiver_and_typed_inputs() {
+    let s = sig("fn f(self: Box<Self>, a: u8, (b, c): (u8, u8))");
+    assert!(s.is_method());
+    assert!(s.receiver().unwrap().colon_token.is_some());
+    let typed: Vec<_> = s.typed_inputs().map(|arg| normalize(arg.pat.to_token_stream())).collect();
+    assert_eq!(typed, [tokens("a"), tokens("(b, c)")]);
+
+    let s = sig("unsafe extern \"C\" fn f(a: u8, ...)");
+    assert!(!s.is_method());
+    assert!(s.has_variadic());
+}
+
+fn normalize_and_forward(src: &str) -> (String, String, String) {
+    let mut s = sig(src);
+    let lets = s.normalize_args();
+    let call = s.forward_call(quote!(inner)).unwrap();
+    (normalize(s.to_token_stream()), normalize(quote!(#(#lets)*)), normalize(call))
+}
+
+#[test]
+fn normalize_args() {
+    let (s, lets, call) = normalize_and_forward("fn f()");
+    assert_eq!((s, lets, call), (tokens("fn f()"), String::new(), tokens("inner()")));
+
+    let (s, lets, call) =
+        normalize_and_forward("fn f(a: u8, mut b: u8, (c, d): (u8, u8), S { e }: S)");
+    assert_eq!(s, tokens("fn f(__arg0: u8, __arg1: u8, __arg2: (u8, u8), __arg3: S)"));
+    assert_eq!(
+        lets,
+        tokens("let a = __arg0; let mut b = __arg1; let (c, d) = __arg2; let S { e } = __arg3;")
+    );
+    assert_eq!(call, tokens("inner(__arg0, __arg1, __arg2, __arg3)"));
+}
+
+#[test]
+fn forward_call_receiver() {
+    let (s, _, call) = normalize_and_forward("fn f(self: Box<Self>, a: u8)");
+    assert_eq!(s, tokens("fn f(self: Box<Self>, __arg0: u8)"));
+    assert_eq!(call, tokens("inner(self, __arg0)"));
+    let (_, _, call) = normalize_and_forward("fn f(&mut self)");
+    assert_eq!(call, tokens("inner(self)"));
+    let (_, _, call) = normalize_and_forward("fn f(#[attr] mut self)");
+    assert_eq!(call, tokens("inner(self)"));
+}
+
+#[test]
+fn forward_call_generics() {
+    let (_, _, call) = normalize_and_forward(
+        "async fn f<'a, T: Clone, const N: usize, U>(a: &'a T, b: [U; N]) -> T where U: Copy, 'a: 'static",
+    );
+    assert_eq!(call, tokens("inner::<T, N, U>(__arg0, __arg1).await"));
+    let (_, _, call) = normalize_and_forward("fn f<'a>(a: &'a u8)");
+    assert_eq!(call, tokens("inner(__arg0)"));
+}
+
+#[test]
+fn forward_call_errors() {
+    let s = sig("fn f(ref a: u8)");
+    assert!(s.forward_call(quote!(inner)).is_err());
+    let s = sig("fn f((a, b): (u8, u8))");
+    assert!(s.forward_call(quote!(inner)).is_err());
+    let mut s = sig("unsafe extern \"C\" fn f(a: u8, ...)");
+    s.normalize_args();
+    assert!(s.forward_call(quote!(inner)).is_err());
+}
+
+fn desugar_async(src: &str, send: bool) -> String {
+    let mut item: ItemFn = syn::parse_str(src).unwrap();
+    item.desugar_async(send, quote!(Captures));
+    normalize(item.to_token_stream())
+}
+
+#[test]
+fn desugar_async_not_async() {
+    assert_eq!(desugar_async("fn f(x: &u8) {}", true), tokens("fn f(x: &u8) {}"));
+}
+
+#[test]
+fn desugar_async_unit() {
+    assert_eq!(
+        desugar_async("async fn f() {}", false),
+        tokens(
+            "fn f<'__fut>() -> impl ::core::future::Future<Output = ()> + '__fut { async move {} }"
+        )
+    );
+    assert_eq!(
+        desugar_async("async fn f(&mut self, x: &u8) { self.0 = *x; }", true),
+        tokens(
+            "fn f<'__fut, '__life0, '__life1>(&'__life0 mut self, __arg0: &'__life1 u8) \
+             -> impl ::core::future::Future<Output = ()> + ::core::marker::Send \
+             + Captures<'__life0> + Captures<'__life1> + '__fut \
+             where '__life0: '__fut, '__life1: '__fut, Self: '__fut \
+             { async move { let x = __arg0; self.0 = *x; } }"
+        )
+    );
+}
+
+#[test]
+fn desugar_async_receiver() {
+    assert_eq!(
+        desugar_async("async fn f(self: Box<Self>) -> u8 { self.0 }", false),
+        tokens(
+            "fn f<'__fut>(self: Box<Self>) -> impl ::core::future::Future<Output = u8> + '__fut \
+             where Self: '__fut { async move { self.0 } }"
+        )
+    );
+}
+
+#[test]
+fn desugar_async_generics() {
+    assert_eq!(
+        desugar_async(
+            "async fn f<'a, T: Clone, const N: usize>(x: &'a T, y: [T; N]) -> T where T: Send { y[0] }",
+            false
+        ),
+        tokens(
+            "fn f<'__fut, 'a, T: Clone, const N: usize>(__arg0: &'a T, __arg1: [T; N]) \
+             -> impl ::core::future::Future<Output = T> + Captures<'a> + '__fut \
+             where T: Send, 'a: '__fut, T: '__fut \
+             { async move { let x = __arg0; let y = __arg1; y[0] } }"
+        )
+    );
+}
+
+#[test]
+fn desugar_async_nested_impl_trait() {
+    assert_eq!(
+        desugar_async("async fn f(it: impl Iterator<Item = impl Display>) {}", false),
+        tokens(
+            "fn f<'__fut>(__arg0: impl Iterator<Item = impl Display + '__fut> + '__fut) \
+             -> impl ::core::future::Future<Output = ()> + '__fut \
+             { async move { let it = __arg0; } }"
+        )
+    );
+}
+
+#[test]
+fn desugar_async_nested_async() {
+    assert_eq!(
+        desugar_async(
+            "async fn f() -> u8 { let g = async { 1 }; let c = || async move { 2 }; g.await + c().await }",
+            false
+        ),
+        tokens(
+            "fn f<'__fut>() -> impl ::core::future::Future<Output = u8> + '__fut \
+             { async move { let g = async { 1 }; let c = || async move { 2 }; g.await + c().await } }"
+        )
+    );
+}
+
+#[test]
+fn desugar_async_compiles() {
+    #[allow(dead_code)]
+    trait Captures<'a> {}
+    impl<T: ?Sized> Captures<'_> for T {}
+    struct S;
+
+    // Compiles the items and returns their tokens, so that the expected
+    // output is also checked by the compiler.
+    macro_rules! compiled {
+        ($($tt:tt)*) => {{
+            #[allow(dead_code, clippy::manual_async_fn, clippy::multiple_bound_locations)]
+            impl S {
+                $($tt)*
+            }
+            tokens(stringify!($($tt)*))
+        }};
+    }
+
+    // `'__life2` is invariant in `&mut Vec<&u8>`, so it must be captured.
+    let expected = compiled! {
+        fn g<'__fut, '__life0, '__life1, '__life2, T: Send + Sync>(
+            &'__life0 self,
+            __arg0: &'__life1 mut Vec<&'__life2 u8>,
+            __arg1: T
+        ) -> impl ::core::future::Future<Output = usize>
+               + ::core::marker::Send
+               + Captures<'__life0>
+               + Captures<'__life1>
+               + Captures<'__life2>
+               + '__fut
+        where
+            T: '__fut,
+            '__life0: '__fut,
+            '__life1: '__fut,
+            '__life2: '__fut,
+            Self: '__fut
+        {
+            async move {
+                let x = __arg0;
+                let t = __arg1;
+                let _ = (self, t);
+                x.len()
+            }
+        }
+    };
+    assert_eq!(
+        desugar_async(
+            "async fn g<T: Send + Sync>(&self, x: &mut Vec<&u8>, t: T) -> usize \
+             { let _ = (self, t); x.len() }",
+            true
+        ),
+        expected
+    );
+}
+
+fn make_lifetimes_explicit(src: &str) -> String {
+    let mut s = sig(src);
+    s.make_lifetimes_explicit().unwrap();
+    normalize(s.to_token_stream())
+}
+
+#[test]
+fn name_elided_lifetimes() {
+    let mut s = sig("fn f(&self, x: &u8, y: Iter<'_, u8>, z: fn(&u8) -> &u8) -> &u8");
+    let elided = s.name_elided_lifetimes();
+    let names: Vec<_> = elided.inputs.iter().map(ToString::to_string).collect();
+    assert_eq!(names, ["'__life0", "'__life1", "'__life2"]);
+    assert_eq!(elided.output.unwrap().to_string(), "'__life0");
+    assert_eq!(
+        normalize(s.to_token_stream()),
+        tokens(
+            "fn f<'__life0, '__life1, '__life2>(&'__life0 self, x: &'__life1 u8, \
+             y: Iter<'__life2, u8>, z: fn(&u8) -> &u8) -> &u8"
+        ),
+    );
+
+    let mut s = sig("fn f(x: &u8, y: &u8) -> &u8");
+    assert!(s.name_elided_lifetimes().output.is_none());
+    assert!(s.make_lifetimes_explicit().is_err());
+}
+
+#[test]
+fn name_elided_lifetimes_order() {
+    assert_eq!(
+        make_lifetimes_explicit("fn f<T>(x: &T) -> &T"),
+        tokens("fn f<'__life0, T>(x: &'__life0 T) -> &'__life0 T"),
+    );
+    assert_eq!(
+        make_lifetimes_explicit("fn f<'a, T, const N: usize>(x: &'a T, y: &[T; N]) -> &'a T"),
+        tokens("fn f<'a, '__life0, T, const N: usize>(x: &'a T, y: &'__life0 [T; N]) -> &'a T"),
+    );
+}
+
+#[test]
+fn name_elided_lifetimes_clash() {
+    assert_eq!(
+        make_lifetimes_explicit("fn f<'__life0, '__life2>(x: &'__life0 u8, y: &u8, z: &u8)"),
+        tokens(
+            "fn f<'__life0, '__life2, '__life1, '__life3>(x: &'__life0 u8, \
+             y: &'__life1 u8, z: &'__life3 u8)"
+        ),
+    );
+    assert_eq!(
+        desugar_async("async fn f<'__life0>(x: &'__life0 u8, y: &u8) {}", false),
+        tokens(
+            "fn f<'__fut, '__life1, '__life0>(__arg0: &'__life0 u8, __arg1: &'__life1 u8) \
+             -> impl ::core::future::Future<Output = ()> + Captures<'__life0> \
+             + Captures<'__life1> + '__fut \
+             where '__life0: '__fut, '__life1: '__fut \
+             { async move { let x = __arg0; let y = __arg1; } }"
+        ),
+    );
+}
+
+fn lift_impl_trait_args(src: &str) -> (String, Vec<(String, usize)>) {
+    let mut s = sig(src);
+    let params = s.lift_impl_trait_args();
+    let params = params.into_iter().map(|p| (p.ident.to_string(), p.input)).collect();
+    (normalize(s.to_token_stream()), params)
+}
+
+#[test]
+fn lift_impl_trait_args_none() {
+    let (s, params) = lift_impl_trait_args("fn f<T: Copy>(x: T) -> impl Copy");
+    assert_eq!(s, tokens("fn f<T: Copy>(x: T) -> impl Copy"));
+    assert!(params.is_empty());
+}
+
+#[test]
+fn lift_impl_trait_args_nested() {
+    let (s, params) = lift_impl_trait_args(
+        "fn f(it: impl Iterator<Item = impl Into<Vec<impl Copy>>>, x: &(impl Debug + 'static))",
+    );
+    assert_eq!(
+        s,
+        tokens(
+            "fn f<T0: Iterator<Item = T1>, T1: Into<Vec<T2>>, T2: Copy, T3: Debug + 'static>\
+             (it: T0, x: &(T3))"
+        ),
+    );
+    assert_eq!(params, [("T0".into(), 0), ("T1".into(), 0), ("T2".into(), 0), ("T3".into(), 1)]);
+}
+
+#[test]
+fn lift_impl_trait_args_generics() {
+    let (s, params) = lift_impl_trait_args(
+        "fn f<'a, T0, const T1: usize>(self: Box<Self>, x: impl Fn(&'a T0) -> [u8; T1], y: T0) \
+         -> impl Sized where T0: Clone",
+    );
+    assert_eq!(
+        s,
+        tokens(
+            "fn f<'a, T0, const T1: usize, T2: Fn(&'a T0) -> [u8; T1]>\
+             (self: Box<Self>, x: T2, y: T0) -> impl Sized where T0: Clone"
+        ),
+    );
+    assert_eq!(params, [("T2".into(), 1)]);
+}
+
+#[test]
+fn block_inner_attrs() {
+    let item: ItemFn =
+        syn::parse_str("#[outer] fn f() { #![allow(unused)] #![doc = \"x\"] let a = 1; a }")
+            .unwrap();
+    assert_eq!(item.block.inner_attrs.len(), 2);
+    assert_eq!(normalize(item.block.stmts.clone()), tokens("let a = 1; a"));
+    assert_eq!(
+        normalize(item.to_token_stream()),
+        tokens("#[outer] fn f() { #![allow(unused)] #![doc = \"x\"] let a = 1; a }")
+    );
+
+    let item: ItemFn = syn::parse_str("fn f() { #[attr] let a = 1; }").unwrap();
+    assert!(item.block.inner_attrs.is_empty());
+    assert_eq!(normalize(item.block.stmts), tokens("#[attr] let a = 1;"));
+}
@@ -0,0 +1,296 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Tests for the rewriting of function bodies.
+
+#![cfg(all(feature = "parsing", feature = "printing"))]
+
+mod common;
+
+use quote::{ToTokens as _, quote};
+use syn_mid::Block;
+
+use self::common::{normalize, tokens};
+
+fn block(src: &str) -> Block {
+    syn::parse_str(src).unwrap()
+}
+
+fn rewrite_returns(src: &str) -> String {
+    let mut block = block(src);
+    block
+        .rewrite_returns(|expr| if expr.is_empty() { quote!(Ok(())) } else { quote!(Ok(#expr)) })
+        .unwrap();
+    normalize(block.to_token_stream())
+}
+
+#[test]
+fn rewrite_returns_trailing_expr() {
+    assert_eq!(
+        rewrite_returns("{ if x < 0 { return -1; } x }"),
+        tokens("{ if x < 0 { return Ok(-1); } Ok(x) }")
+    );
+    assert_eq!(rewrite_returns("{ a; b }"), tokens("{ a; Ok(b) }"));
+    assert_eq!(rewrite_returns("{ m!() }"), tokens("{ Ok(m!()) }"));
+    assert_eq!(
+        rewrite_returns("{ match x { 0 => return 1, _ => 2 } }"),
+        tokens("{ Ok(match x { 0 => return Ok(1), _ => 2 }) }")
+    );
+    assert_eq!(
+        rewrite_returns("{ match x { S { a } | T { a } => return a, U => {} | V => {} } }"),
+        tokens("{ Ok(match x { S { a } | T { a } => return Ok(a), U => {} | V => {} }) }")
+    );
+}
+
+#[test]
+fn rewrite_returns_closure() {
+    assert_eq!(rewrite_returns("{ return |a, b| a + b; }"), tokens("{ return Ok(|a, b| a + b); }"));
+    assert_eq!(
+        rewrite_returns(
+            "{ if c { return move |a: (u8, u8)| -> R<A, B> { a.0 }; } f(|x| { return x; }) }"
+        ),
+        tokens(
+            "{ if c { return Ok(move |a: (u8, u8)| -> R<A, B> { a.0 }); } Ok(f(|x| { return x; })) }"
+        )
+    );
+}
+
+#[test]
+fn rewrite_returns_unit() {
+    assert_eq!(rewrite_returns("{}"), tokens("{ Ok(()) }"));
+    assert_eq!(rewrite_returns("{ a(); }"), tokens("{ a(); Ok(()) }"));
+    assert_eq!(rewrite_returns("{ let x = 1; }"), tokens("{ let x = 1; Ok(()) }"));
+    assert_eq!(
+        rewrite_returns("{ if c { return; } a(); }"),
+        tokens("{ if c { return Ok(()); } a(); Ok(()) }")
+    );
+    assert_eq!(rewrite_returns("{ a(); return; }"), tokens("{ a(); return Ok(()); }"));
+    assert_eq!(rewrite_returns("{ return }"), tokens("{ return Ok(()) }"));
+    assert_eq!(rewrite_returns("{ m!(); }"), tokens("{ m!(); Ok(()) }"));
+    assert_eq!(rewrite_returns("{ fn f() {} }"), tokens("{ fn f() {} Ok(()) }"));
+}
+
+#[test]
+fn rewrite_returns_nested_return() {
+    assert_eq!(rewrite_returns("{ return return 1; }"), tokens("{ return Ok(return Ok(1)); }"));
+    assert_eq!(
+        rewrite_returns("{ f(if c { return 1 } else { 2 }); }"),
+        tokens("{ f(if c { return Ok(1) } else { 2 }); Ok(()) }")
+    );
+    assert_eq!(
+        rewrite_returns("{ loop { if c { return 1; } } }"),
+        tokens("{ Ok(loop { if c { return Ok(1); } }) }")
+    );
+}
+
+#[test]
+fn rewrite_returns_other_scopes() {
+    assert_eq!(
+        rewrite_returns("{ let g = || { return 1; }; let h = |x| return x; g() }"),
+        tokens("{ let g = || { return 1; }; let h = |x| return x; Ok(g()) }")
+    );
+    assert_eq!(
+        rewrite_returns(
+            "{ let g = move |x: u8| -> u8 { if x > 0 { return x; } 0 }; return g(1); }"
+        ),
+        tokens("{ let g = move |x: u8| -> u8 { if x > 0 { return x; } 0 }; return Ok(g(1)); }")
+    );
+    assert_eq!(
+        rewrite_returns(
+            "{ let fut = async { return 1; }; let c = async move || { return 2; }; fut }"
+        ),
+        tokens("{ let fut = async { return 1; }; let c = async move || { return 2; }; Ok(fut) }")
+    );
+    assert_eq!(
+        rewrite_returns("{ fn inner() -> u8 { return 1; } impl S { fn f() { return; } } inner() }"),
+        tokens("{ fn inner() -> u8 { return 1; } impl S { fn f() { return; } } Ok(inner()) }")
+    );
+    assert_eq!(
+        rewrite_returns("{ m!(return 1); vec![return 2]; x }"),
+        tokens("{ m!(return 1); vec![return 2]; Ok(x) }")
+    );
+    assert_eq!(
+        rewrite_returns("{ let g = || || { return 1; }; a || { return 2 } }"),
+        tokens("{ let g = || || { return 1; }; Ok(a || { return Ok(2) }) }")
+    );
+}
+
+fn rewrite_try_exprs(src: &str) -> String {
+    let mut block = block(src);
+    block.rewrite_try_exprs(|expr| quote!(W(#expr))).unwrap();
+    normalize(block.to_token_stream())
+}
+
+#[test]
+fn rewrite_try_exprs_receiver() {
+    assert_eq!(rewrite_try_exprs("{ a? }"), tokens("{ W(a)? }"));
+    assert_eq!(
+        rewrite_try_exprs("{ x + a.b::<T>()[0]?.c? }"),
+        tokens("{ x + W(W(a.b::<T>()[0])?.c)? }")
+    );
+    assert_eq!(rewrite_try_exprs("{ &x.await? }"), tokens("{ &W(x.await)? }"));
+    assert_eq!(rewrite_try_exprs("{ <T as Tr>::f()? }"), tokens("{ W(<T as Tr>::f())? }"));
+    assert_eq!(rewrite_try_exprs("{ ::a::b()? }"), tokens("{ W(::a::b())? }"));
+    assert_eq!(rewrite_try_exprs("{ m!(a?)? }"), tokens("{ W(m!(W(a)?))? }"));
+    assert_eq!(rewrite_try_exprs("{ f(a?, (b?))? }"), tokens("{ W(f(W(a)?, (W(b)?)))? }"));
+    assert_eq!(rewrite_try_exprs("{ a..b? }"), tokens("{ a..W(b)? }"));
+}
+
+#[test]
+fn rewrite_try_exprs_block_like() {
+    assert_eq!(
+        rewrite_try_exprs("{ match x { _ => a? }? }"),
+        tokens("{ W(match x { _ => W(a)? })? }")
+    );
+    assert_eq!(
+        rewrite_try_exprs("{ if c { a } else { b }.f()? }"),
+        tokens("{ W(if c { a } else { b }.f())? }")
+    );
+    assert_eq!(rewrite_try_exprs("{ if c {} (a)? }"), tokens("{ if c {} W((a))? }"));
+    assert_eq!(rewrite_try_exprs("{ loop {} [a][0]? }"), tokens("{ loop {} W([a][0])? }"));
+    assert_eq!(
+        rewrite_try_exprs("{ unsafe { a } <T>::f()? }"),
+        tokens("{ unsafe { a } W(<T>::f())? }")
+    );
+    assert_eq!(rewrite_try_exprs("{ if c {} if d { a? } }"), tokens("{ if c {} if d { W(a)? } }"));
+}
+
+#[test]
+fn rewrite_try_exprs_label() {
+    assert_eq!(
+        rewrite_try_exprs("{ 'a: loop { break 'a (x)?; } }"),
+        tokens("{ 'a: loop { break 'a W((x))?; } }")
+    );
+    assert_eq!(
+        rewrite_try_exprs("{ 'a: loop { break 'a x?; } }"),
+        tokens("{ 'a: loop { break 'a W(x)?; } }")
+    );
+    assert_eq!(
+        rewrite_try_exprs("{ 'a: loop { continue 'a; } }"),
+        tokens("{ 'a: loop { continue 'a; } }")
+    );
+    assert_eq!(
+        rewrite_try_exprs("{ 'a: { break 'a f()?.g()? } }"),
+        tokens("{ 'a: { break 'a W(W(f())?.g())? } }")
+    );
+}
+
+#[test]
+fn rewrite_try_exprs_other_scopes() {
+    assert_eq!(
+        rewrite_try_exprs("{ let g = || a?; let h = |x| { x? }; let i = async { b? }; c? }"),
+        tokens("{ let g = || a?; let h = |x| { x? }; let i = async { b? }; W(c)? }")
+    );
+    assert_eq!(
+        rewrite_try_exprs("{ fn f() -> R { a? } let j = async move || b?; j().await? }"),
+        tokens("{ fn f() -> R { a? } let j = async move || b?; W(j().await)? }")
+    );
+}
+
+#[test]
+fn rewrite_try_exprs_macro_args() {
+    assert_eq!(
+        rewrite_try_exprs(r#"{ println!("{}", x?); vec![a?, b.c()?] }"#),
+        tokens(r#"{ println!("{}", W(x)?); vec![W(a)?, W(b.c())?] }"#)
+    );
+    assert_eq!(rewrite_try_exprs("{ vec![a?; n?] }"), tokens("{ vec![W(a)?; W(n)?] }"));
+    assert_eq!(
+        rewrite_try_exprs("{ macro_rules! m { ($($a:tt)?) => {}; } m!(|| a?) }"),
+        tokens("{ macro_rules! m { ($($a:tt)?) => {}; } m!(|| a?) }")
+    );
+}
+
+fn substitute(src: &str, name: &str, ty: &str) -> String {
+    let mut block = block(src);
+    let name = syn::Ident::new(name, proc_macro2::Span::call_site());
+    let substs = [(name, syn::parse_str(ty).unwrap())];
+    block.substitute(&substs).unwrap();
+    normalize(block.to_token_stream())
+}
+
+#[test]
+fn substitute_types() {
+    assert_eq!(
+        substitute("{ let v: Vec<T> = Vec::<T>::new(); T::from(v) }", "T", "String"),
+        tokens("{ let v: Vec<String> = Vec::<String>::new(); String::from(v) }")
+    );
+    assert_eq!(
+        substitute("{ let x: &T = &T::default(); x.T; m::T; T!(); S { T: 0 } }", "T", "[u8; 4]"),
+        tokens("{ let x: &[u8; 4] = &<[u8; 4]>::default(); x.T; m::T; T!(); S { T: 0 } }")
+    );
+    assert_eq!(
+        substitute("{ let x: Box<T> = f::<T>(); }", "T", "dyn A + B"),
+        tokens("{ let x: Box<(dyn A + B)> = f::<(dyn A + B)>(); }")
+    );
+}
+
+#[test]
+fn substitute_generic_path() {
+    assert_eq!(
+        substitute("{ let s: Self = Self::new(); Self { a: 1 } }", "Self", "Foo<u8>"),
+        tokens("{ let s: Foo::<u8> = Foo::<u8>::new(); Foo::<u8> { a: 1 } }")
+    );
+    assert_eq!(
+        substitute("{ let Self { a } = y; Self(a) }", "Self", "m::Foo<'a, u8>"),
+        tokens("{ let m::Foo::<'a, u8> { a } = y; m::Foo::<'a, u8>(a) }")
+    );
+    assert_eq!(
+        substitute(
+            "{ match x { Self::A(x) => Self::B { x }, Self::B { x } | Self::C(x, ..) => Self::A(x) } }",
+            "Self",
+            "E<T>",
+        ),
+        tokens(
+            "{ match x { E::<T>::A(x) => E::<T>::B { x }, \
+             E::<T>::B { x } | E::<T>::C(x, ..) => E::<T>::A(x) } }"
+        )
+    );
+}
+
+#[test]
+fn substitute_nested_items() {
+    assert_eq!(
+        substitute(
+            "{ struct W<T>(T); enum E<T> { A(T) } type A<T> = Vec<T>; \
+             union U<T: Copy> { t: T } const C: Option<T> = None; W(T::new()) }",
+            "T",
+            "u8",
+        ),
+        tokens(
+            "{ struct W<T>(T); enum E<T> { A(T) } type A<T> = Vec<T>; \
+             union U<T: Copy> { t: T } const C: Option<T> = None; W(u8::new()) }"
+        )
+    );
+    assert_eq!(
+        substitute(
+            "{ let f: unsafe extern \"C\" fn(T) -> T = g; let p: *const T = &raw const x; \
+             let s: &'static T = h(); }",
+            "T",
+            "u8",
+        ),
+        tokens(
+            "{ let f: unsafe extern \"C\" fn(u8) -> u8 = g; let p: *const u8 = &raw const x; \
+             let s: &'static u8 = h(); }"
+        )
+    );
+}
+
+#[test]
+fn substitute_named_macro_args() {
+    assert_eq!(
+        substitute(r#"{ format!("{T}", T = T::default()) }"#, "T", "String"),
+        tokens(r#"{ format!("{T}", T = String::default()) }"#)
+    );
+    assert_eq!(substitute("{ x == T::A }", "T", "E"), tokens("{ x == E::A }"));
+}
+
+#[test]
+fn substitute_shorthand_fields() {
+    assert_eq!(
+        substitute("{ let S { T, a } = s; S { a, T } }", "T", "u8"),
+        tokens("{ let S { T, a } = s; S { a, T } }")
+    );
+    assert_eq!(
+        substitute("{ let x = { Self }; S { a: Self } }", "Self", "Foo"),
+        tokens("{ let x = { Foo }; S { a: Foo } }")
+    );
+}
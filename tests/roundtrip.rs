@@ -0,0 +1,129 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Round-trip tests over a corpus of Rust source files.
+//
+// For every function in the `.rs` files under tests/corpus, this parses the
+// function with `syn_mid::ItemFn`, prints it, parses the printed tokens again,
+// and checks that the tokens are the same each time. It also checks that
+// splitting the body into statements and printing them gives the same tokens.
+//
+// The files in tests/corpus are written for this crate, one per area of the
+// syntax it parses (see tests/corpus/README.md); they are not taken from
+// rust-lang/rust. The functions are found with syn's "full" parser, so a
+// corpus can contain any Rust code syn can parse. Set SYN_MID_TEST_CORPUS to a
+// directory to run the tests over a larger corpus, such as tests/ui in a
+// checkout of rust-lang/rust. Files in that corpus that syn cannot parse are
+// skipped, since some of those tests are intentionally invalid.
+
+#![cfg(all(feature = "parsing", feature = "printing"))]
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use proc_macro2::TokenStream;
+use quote::ToTokens as _;
+use syn::visit::{self, Visit};
+
+struct Fns(Vec<TokenStream>);
+
+impl<'ast> Visit<'ast> for Fns {
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        self.0.push(node.to_token_stream());
+        visit::visit_item_fn(self, node);
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast syn::ImplItemFn) {
+        // `default fn` is not a free-standing function.
+        if node.defaultness.is_none() {
+            self.0.push(node.to_token_stream());
+        }
+        visit::visit_impl_item_fn(self, node);
+    }
+
+    fn visit_trait_item_fn(&mut self, node: &'ast syn::TraitItemFn) {
+        if node.default.is_some() {
+            self.0.push(node.to_token_stream());
+        }
+        visit::visit_trait_item_fn(self, node);
+    }
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+}
+
+fn roundtrip(tokens: &TokenStream) -> Result<(), String> {
+    fn check(what: &str, actual: &TokenStream, expected: &TokenStream) -> Result<(), String> {
+        let (actual, expected) = (actual.to_string(), expected.to_string());
+        if actual == expected {
+            Ok(())
+        } else {
+            Err(format!("{what} differ:\n  expected: {expected}\n  actual:   {actual}"))
+        }
+    }
+
+    let item: syn_mid::ItemFn = syn::parse2(tokens.clone()).map_err(|e| e.to_string())?;
+    let printed = item.to_token_stream();
+    check("printed tokens", &printed, tokens)?;
+
+    let reparsed: syn_mid::ItemFn = syn::parse2(printed.clone()).map_err(|e| e.to_string())?;
+    check("reprinted tokens", &reparsed.to_token_stream(), &printed)?;
+
+    let mut stmts = TokenStream::new();
+    for stmt in item.block.split_stmts().map_err(|e| e.to_string())? {
+        stmt.to_tokens(&mut stmts);
+    }
+    check("statements", &stmts, &item.block.stmts)
+}
+
+#[test]
+fn corpus() {
+    let external = env::var_os("SYN_MID_TEST_CORPUS");
+    let strict = external.is_none();
+    let dir = external
+        .map_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/corpus"), PathBuf::from);
+    let mut files = vec![];
+    collect_files(&dir, &mut files);
+    files.sort();
+
+    let mut count = 0;
+    let mut failures = vec![];
+    for file in &files {
+        let ast = match fs::read_to_string(file)
+            .map_err(|e| e.to_string())
+            .and_then(|src| syn::parse_file(&src).map_err(|e| e.to_string()))
+        {
+            Ok(ast) => ast,
+            Err(e) if strict => {
+                failures.push(format!("{}: not parsed by syn: {e}", file.display()));
+                continue;
+            }
+            Err(_) => continue,
+        };
+        let mut fns = Fns(vec![]);
+        fns.visit_file(&ast);
+        for tokens in fns.0 {
+            count += 1;
+            if let Err(e) = roundtrip(&tokens) {
+                failures.push(format!("{}: {e}: {tokens}", file.display()));
+            }
+        }
+    }
+
+    assert!(count > 0, "no functions found in {}", dir.display());
+    assert!(
+        failures.is_empty(),
+        "{} of {count} functions failed:\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
@@ -0,0 +1,73 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Tests for the Serialize and Deserialize impls.
+
+#![cfg(feature = "serde")]
+
+mod common;
+
+use quote::ToTokens;
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::json;
+use syn::parse::Parse;
+use syn_mid::{ExprMatch, ItemFn, Receiver, Stmt};
+
+use self::common::{normalize, tokens};
+
+/// Parses `src`, and checks that the tree round-trips through JSON.
+fn roundtrip<T>(src: &str)
+where
+    T: Parse + ToTokens + Serialize + DeserializeOwned,
+{
+    let node: T = syn::parse_str(src).unwrap();
+    let json = serde_json::to_string(&node).unwrap();
+    let node: T = serde_json::from_str(&json).unwrap();
+    assert_eq!(normalize(node.to_token_stream()), tokens(src), "{json}");
+}
+
+#[test]
+fn item_fn() {
+    roundtrip::<ItemFn>(
+        "#[inline] pub(crate) const unsafe fn f<'a, T: Clone>(&'a self, (a, _): (T, u8), \
+         S { x, .. }: S) -> Option<T> where T: Copy { #![allow(unused)] let y = x; Some(a) }",
+    );
+    roundtrip::<ItemFn>("extern \"C\" fn f(a: u8, mut b: &[u8], ...) {}");
+}
+
+#[test]
+fn stmt() {
+    roundtrip::<Stmt>("let (a, ref mut b): (u8, u8) = f()? else { return };");
+    roundtrip::<Stmt>("#[cfg(x)] m! { a b }");
+    roundtrip::<Stmt>("struct S<T>(T);");
+    roundtrip::<Stmt>("x += 1;");
+}
+
+#[test]
+fn expr_match() {
+    roundtrip::<ExprMatch>(
+        "match x { Some(0) | None => {} S { a, b: (c, _), .. } if a > 0 => a, T::U(..) => 1 }",
+    );
+}
+
+#[test]
+fn receiver_format() {
+    let receiver: Receiver = syn::parse_str("&'a mut self").unwrap();
+    assert_eq!(
+        serde_json::to_value(&receiver).unwrap(),
+        json!({
+            "ref": true,
+            "lifetime": "'a",
+            "mut": true,
+            "ty": "& 'a mut Self",
+        })
+    );
+
+    let receiver: Receiver = syn::parse_str("self: Box<Self>").unwrap();
+    assert_eq!(
+        serde_json::to_value(&receiver).unwrap(),
+        json!({
+            "colon_token": true,
+            "ty": "Box < Self >",
+        })
+    );
+}
@@ -0,0 +1,139 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Tests for splitting a block into top-level statements.
+
+#![cfg(all(feature = "parsing", feature = "printing"))]
+
+use proc_macro2::TokenStream;
+use quote::{ToTokens as _, format_ident};
+use syn_mid::{Block, ItemFn, Pat, Stmt};
+
+fn tokens(src: &str) -> String {
+    src.parse::<TokenStream>().unwrap().to_string()
+}
+
+fn split(src: &str) -> Vec<Stmt> {
+    let block: Block = syn::parse_str(&format!("{{ {src} }}")).unwrap();
+    block.split_stmts().unwrap()
+}
+
+fn split_items(src: &str) -> Vec<String> {
+    split(src)
+        .into_iter()
+        .map(|stmt| match stmt {
+            Stmt::Item(item) => item.to_string(),
+            stmt => panic!("expected item, found `{}`", stmt.to_token_stream()),
+        })
+        .collect()
+}
+
+fn assert_items(src: &str, expected: &[&str]) {
+    let expected: Vec<_> = expected.iter().map(|s| tokens(s)).collect();
+    assert_eq!(split_items(src), expected);
+}
+
+#[test]
+fn item_initializer_with_comparison() {
+    assert_items(
+        "const X: bool = 1 < 2; const Y: bool = 2 > 1;",
+        &["const X: bool = 1 < 2;", "const Y: bool = 2 > 1;"],
+    );
+    assert_items(
+        "static S: u8 = 1 << 2; static mut T: bool = a < b && c > d;",
+        &["static S: u8 = 1 << 2;", "static mut T: bool = a < b && c > d;"],
+    );
+    assert_items(
+        "const Z: Foo<u8> = Foo::<u8>::new(1 < 2); type A = B<C>;",
+        &["const Z: Foo<u8> = Foo::<u8>::new(1 < 2);", "type A = B<C>;"],
+    );
+}
+
+#[test]
+fn item_initializer_before_other_stmts() {
+    let stmts = split("const X: bool = 1 < 2; let a = X; a");
+    assert_eq!(stmts.len(), 3);
+    assert!(matches!(stmts[0], Stmt::Item(_)));
+    assert!(matches!(stmts[1], Stmt::Local(_)));
+    assert!(matches!(stmts[2], Stmt::Expr(_, None)));
+}
+
+#[test]
+fn item_with_generics() {
+    assert_items(
+        "fn f<const N: usize>() -> Foo<{ N }> { a < b } \
+         struct S<T = Box<dyn Fn() -> u8>>(T); \
+         impl<T> Tr<{ 1 }> for S<T> where T: Fn() -> u8 {}",
+        &[
+            "fn f<const N: usize>() -> Foo<{ N }> { a < b }",
+            "struct S<T = Box<dyn Fn() -> u8>>(T);",
+            "impl<T> Tr<{ 1 }> for S<T> where T: Fn() -> u8 {}",
+        ],
+    );
+}
+
+#[test]
+fn nested_fns_after_item_initializer() {
+    let block: Block = syn::parse_str("{ static S: u8 = 1 << 2; fn f() {} }").unwrap();
+    let fns = block.nested_fns().unwrap();
+    assert_eq!(fns.len(), 1);
+    assert_eq!(fns[0].sig.ident, "f");
+}
+
+#[test]
+fn local_with_unsupported_pattern() {
+    let stmts = split("let [a, b] = x; let [c, ..]: [u8; 3] = y; let 1..=5 = z else { return };");
+    let pats: Vec<_> = stmts
+        .iter()
+        .map(|stmt| match stmt {
+            Stmt::Local(local) => local.pat.to_token_stream().to_string(),
+            stmt => panic!("expected local, found `{}`", stmt.to_token_stream()),
+        })
+        .collect();
+    assert_eq!(pats, [tokens("[a, b]"), tokens("[c, ..]: [u8; 3]"), tokens("1..=5")]);
+    match &stmts[1] {
+        Stmt::Local(local) => match &local.pat {
+            Pat::Type(pat) => assert!(matches!(*pat.pat, Pat::Verbatim(_))),
+            pat => panic!("expected type ascription, found `{}`", pat.to_token_stream()),
+        },
+        _ => unreachable!(),
+    }
+}
+
+fn update_nested_fns(src: &str, f: fn(&mut ItemFn)) -> String {
+    let mut block: Block = syn::parse_str(&format!("{{ {src} }}")).unwrap();
+    block.update_nested_fns(f).unwrap();
+    block.stmts.to_string()
+}
+
+fn rename(item: &mut ItemFn) {
+    item.sig.ident = format_ident!("{}_new", item.sig.ident);
+}
+
+#[test]
+fn update_nested_fns_keeps_other_stmts() {
+    // The other statements keep their tokens, including the spacing of
+    // punctuation (`1..=2`, `1<<2`) and empty statements.
+    let src = "let x=1..=2 ;x.y(|a|a)?;;#[cfg(x)] let [b, ..]:[u8;2]=c else{return};\
+               fn f(){g()} m!{} static S:u8=1<<2; struct S<T>(T); 'a:loop{} z";
+    let expected = "let x=1..=2 ;x.y(|a|a)?;;#[cfg(x)] let [b, ..]:[u8;2]=c else{return};\
+                    fn f_new(){g()} m!{} static S:u8=1<<2; struct S<T>(T); 'a:loop{} z";
+    assert_eq!(update_nested_fns(src, rename), tokens(expected));
+}
+
+#[test]
+fn update_nested_fns_qualifiers() {
+    let src = "pub(crate) unsafe extern \"C\" fn a(x: u8) {} const fn b() {} \
+               #[inline] pub async unsafe fn c() {} extern crate d; extern \"C\" {} const E: u8 = 0;";
+    let expected = "pub(crate) unsafe extern \"C\" fn a_new(x: u8) {} const fn b_new() {} \
+                    #[inline] pub async unsafe fn c_new() {} extern crate d; extern \"C\" {} \
+                    const E: u8 = 0;";
+    assert_eq!(update_nested_fns(src, rename), tokens(expected));
+
+    let block: Block = syn::parse_str(&format!("{{ {src} }}")).unwrap();
+    let fns = block.nested_fns().unwrap();
+    let idents: Vec<_> = fns.iter().map(|item| item.sig.ident.to_string()).collect();
+    assert_eq!(idents, ["a", "b", "c"]);
+    assert!(fns[0].sig.unsafety.is_some() && fns[0].sig.abi.is_some());
+    assert!(fns[1].sig.constness.is_some());
+    assert!(fns[2].sig.asyncness.is_some());
+}
@@ -0,0 +1,162 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Tests for the syntax tree traversals of the statements and `match`
+// expressions in a block.
+
+#![cfg(all(
+    feature = "parsing",
+    feature = "printing",
+    any(feature = "visit", feature = "visit-mut", feature = "fold"),
+))]
+
+#[cfg(any(feature = "visit-mut", feature = "fold"))]
+mod common;
+
+use syn_mid::Block;
+
+#[cfg(any(feature = "visit-mut", feature = "fold"))]
+use self::common::{normalize, tokens};
+
+const BLOCK: &str = "{
+    let (a, Some(b)): (u8, Option<u8>) = x else { return };
+    let S { x: c, y: &mut e, .. } = s;
+    m!(f);
+    match x {
+        Some((g, ref mut h)) | Err(T::U(g, h)) if g > 0 => {}
+        S { z: i, .. } => i,
+    }
+}";
+
+fn block() -> Block {
+    syn::parse_str(BLOCK).unwrap()
+}
+
+#[cfg(feature = "visit")]
+#[test]
+fn visit_nested() {
+    use syn_mid::{
+        PatIdent,
+        visit::{self, Visit},
+    };
+
+    struct Bindings(Vec<String>);
+
+    impl<'ast> Visit<'ast> for Bindings {
+        fn visit_pat_ident(&mut self, node: &'ast PatIdent) {
+            self.0.push(node.ident.to_string());
+            visit::visit_pat_ident(self, node);
+        }
+    }
+
+    let block = block();
+    let mut v = Bindings(Vec::new());
+    for stmt in &block.split_stmts().unwrap() {
+        v.visit_stmt(stmt);
+    }
+    assert_eq!(v.0, ["a", "b", "c", "e"]);
+
+    let mut v = Bindings(Vec::new());
+    for expr in &block.top_level_matches().unwrap() {
+        v.visit_expr_match(expr);
+    }
+    assert_eq!(v.0, ["g", "h", "g", "h", "i"]);
+}
+
+#[cfg(feature = "visit-mut")]
+#[test]
+fn visit_mut_nested() {
+    use quote::{ToTokens, format_ident};
+    use syn_mid::{
+        PatIdent,
+        visit_mut::{self, VisitMut},
+    };
+
+    // Adds `_` to the start of bindings.
+    struct Unused;
+
+    impl VisitMut for Unused {
+        fn visit_pat_ident_mut(&mut self, node: &mut PatIdent) {
+            node.ident = format_ident!("_{}", node.ident);
+            visit_mut::visit_pat_ident_mut(self, node);
+        }
+    }
+
+    let block = block();
+    let mut stmts = block.split_stmts().unwrap();
+    for stmt in &mut stmts {
+        Unused.visit_stmt_mut(stmt);
+    }
+    assert_eq!(
+        normalize(stmts.iter().map(ToTokens::to_token_stream).collect()),
+        tokens(
+            "let (_a, Some(_b)): (u8, Option<u8>) = x else { return }; \
+             let S { x: _c, y: &mut _e, .. } = s; \
+             m!(f); \
+             match x { Some((g, ref mut h)) | Err(T::U(g, h)) if g > 0 => {} S { z: i, .. } => i, }"
+        )
+    );
+
+    let mut exprs = block.top_level_matches().unwrap();
+    for expr in &mut exprs {
+        Unused.visit_expr_match_mut(expr);
+    }
+    assert_eq!(
+        normalize(exprs.iter().map(ToTokens::to_token_stream).collect()),
+        tokens(
+            "match x { Some((_g, ref mut _h)) | Err(T::U(_g, _h)) if g > 0 => {} \
+             S { z: _i, .. } => i, }"
+        )
+    );
+}
+
+#[cfg(feature = "fold")]
+#[test]
+fn fold_nested() {
+    use quote::ToTokens;
+    use syn::Token;
+    use syn_mid::{
+        Pat, PatWild,
+        fold::{self, Fold},
+    };
+
+    // Replaces the bindings `b`, `e`, and `h` with `_`.
+    struct Ignore;
+
+    impl Fold for Ignore {
+        fn fold_pat(&mut self, node: Pat) -> Pat {
+            match node {
+                Pat::Ident(pat) if ["b", "e", "h"].iter().any(|name| pat.ident == name) => {
+                    Pat::Wild(PatWild {
+                        attrs: pat.attrs,
+                        underscore_token: Token![_](pat.ident.span()),
+                    })
+                }
+                node => fold::fold_pat(self, node),
+            }
+        }
+    }
+
+    let block = block();
+    let stmts: Vec<_> =
+        block.split_stmts().unwrap().into_iter().map(|stmt| Ignore.fold_stmt(stmt)).collect();
+    assert_eq!(
+        normalize(stmts.iter().map(ToTokens::to_token_stream).collect()),
+        tokens(
+            "let (a, Some(_)): (u8, Option<u8>) = x else { return }; \
+             let S { x: c, y: &mut _, .. } = s; \
+             m!(f); \
+             match x { Some((g, ref mut h)) | Err(T::U(g, h)) if g > 0 => {} S { z: i, .. } => i, }"
+        )
+    );
+
+    let exprs: Vec<_> = block
+        .top_level_matches()
+        .unwrap()
+        .into_iter()
+        .map(|expr| Ignore.fold_expr_match(expr))
+        .collect();
+    assert_eq!(
+        normalize(exprs.iter().map(ToTokens::to_token_stream).collect()),
+        tokens("match x { Some((g, _)) | Err(T::U(g, _)) if g > 0 => {} S { z: i, .. } => i, }")
+    );
+}